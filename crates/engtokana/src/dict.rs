@@ -0,0 +1,108 @@
+use std::{collections::HashMap, fmt};
+
+/// バイナリに同梱する辞書
+pub(crate) const BUNDLED_DIC: &str = include_str!("../dict/bundled.dic");
+
+/// 辞書の読み込み結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DictLoadReport {
+    /// 読み込んだ単語の数 (重複を除く)
+    pub entries: usize,
+
+    /// 「英単語 読み」の形式になっていなかった行
+    pub malformed: Vec<MalformedLine>,
+}
+
+/// 辞書の形式になっていなかった行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedLine {
+    /// 辞書の名前 (ファイルのパスなど)
+    pub source: String,
+
+    /// 1 から数えた行番号
+    pub line_number: usize,
+    pub line: String,
+}
+
+impl fmt::Display for MalformedLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {:?}", self.source, self.line_number, self.line)
+    }
+}
+
+/// 辞書の文字列を読み込んで dict に追加する (すでにある単語は上書きする)
+pub(crate) fn parse_dic(
+    dict: &mut HashMap<String, String>,
+    text: &str,
+    source: &str,
+) -> Vec<MalformedLine> {
+    let mut malformed = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        // 空白行、コメント行は飛ばす
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // 最初の空白で英単語と読みに分割する
+        let split = line
+            .split_once(char::is_whitespace)
+            .map(|(word, reading)| (word, reading.trim()))
+            .filter(|(word, reading)| word.is_ascii() && !reading.is_empty());
+
+        match split {
+            Some((word, reading)) => {
+                dict.insert(word.to_uppercase(), reading.to_string());
+            }
+            None => malformed.push(MalformedLine {
+                source: source.to_string(),
+                line_number: i + 1,
+                line: line.to_string(),
+            }),
+        }
+    }
+
+    malformed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dic() {
+        let mut dict = HashMap::new();
+        let text = "# comment\r\nHELLO ハロー\r\n\nworld  ワールド \nBROKEN\nこんにちは ハロー\n";
+
+        let malformed = parse_dic(&mut dict, text, "test.dic");
+
+        assert_eq!(dict.get("HELLO").map(|s| s.as_str()), Some("ハロー"));
+        assert_eq!(dict.get("WORLD").map(|s| s.as_str()), Some("ワールド"));
+        assert_eq!(dict.len(), 2);
+
+        let line_numbers: Vec<_> = malformed.iter().map(|line| line.line_number).collect();
+        assert_eq!(line_numbers, vec![5, 6]);
+        assert_eq!(malformed[0].to_string(), "test.dic:5: \"BROKEN\"");
+    }
+
+    #[test]
+    fn test_overlay() {
+        let mut dict = HashMap::new();
+        parse_dic(&mut dict, "HELLO ハロー\nWORLD ワールド", "base");
+        parse_dic(&mut dict, "hello ハロウ", "overlay");
+
+        assert_eq!(dict.get("HELLO").map(|s| s.as_str()), Some("ハロウ"));
+        assert_eq!(dict.get("WORLD").map(|s| s.as_str()), Some("ワールド"));
+    }
+
+    #[test]
+    fn test_bundled_dic() {
+        let mut dict = HashMap::new();
+        let malformed = parse_dic(&mut dict, BUNDLED_DIC, "bundled");
+
+        assert!(malformed.is_empty(), "{malformed:?}");
+        assert_eq!(dict.get("HELLO").map(|s| s.as_str()), Some("ハロー"));
+    }
+}
@@ -1,87 +1,193 @@
+mod dict;
+mod romaji;
+mod segment;
+mod spell;
+
+pub use dict::{DictLoadReport, MalformedLine};
+
 use std::{
+    borrow::Cow,
     collections::HashMap,
-    path::PathBuf,
-    sync::{LazyLock, RwLock, RwLockReadGuard},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, LazyLock, RwLock,
+    },
 };
 
-use tokio::{
-    fs::File,
-    io::{AsyncReadExt as _, AsyncWriteExt as _},
-};
+use tokio::{fs::File, io::AsyncWriteExt as _};
 
-// 辞書のパスを設定する (下のURLの bep-eng.dic を入れる)
+use dict::{parse_dic, BUNDLED_DIC};
+use romaji::{parse_romaji, ROMAJI_THRESHOLD};
+use segment::TransDict;
+use spell::{spell_out, split_identifier};
+
+// 追加でダウンロードできる辞書 (下のURLの bep-eng.dic)
 // https://fastapi.metacpan.org/source/MASH/Lingua-JA-Yomi-0.01/lib/Lingua/JA
 const BEPENG_DIC_URL: &str =
     "https://fastapi.metacpan.org/source/MASH/Lingua-JA-Yomi-0.01/lib/Lingua/JA/bep-eng.dic";
 
-static TRANS_DICT_MAP: LazyLock<RwLock<HashMap<String, String>>> =
-    LazyLock::new(|| RwLock::new(HashMap::new()));
+// ユーザー辞書がない場合に作成するファイルの内容
+const OVERLAY_TEMPLATE: &str = "\
+# engtokana のユーザー辞書 (同梱の辞書や bep-eng.dic より優先される)
+# 1行に「英単語 読み」を空白で区切って書く
+# 例: SONORUST ソノラスト
+";
+
+// 初期化する前でも同梱の辞書で変換できるようにする
+// (変換中に辞書を更新できるように、変換するときは Arc を複製してすぐにロックを外す)
+static TRANS_DICT: LazyLock<RwLock<Arc<TransDict>>> = LazyLock::new(|| {
+    let mut trans_dict = HashMap::new();
+    parse_dic(&mut trans_dict, BUNDLED_DIC, "bundled.dic");
+
+    RwLock::new(Arc::new(TransDict::new(trans_dict)))
+});
+
+// 辞書より1文字ずつ読むことを優先する、大文字だけの単語 (略語) の最大の長さ (0 なら辞書を優先する)
+static ACRONYM_MAX_LEN: AtomicUsize = AtomicUsize::new(3);
+
+// 辞書にない単語を1文字ずつ読む最大の長さ (0 なら1文字ずつ読まない)
+static SPELL_MAX_LEN: AtomicUsize = AtomicUsize::new(6);
+
+/// 変換するときの設定
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConvertOptions {
+    /// ローマ字らしい単語を英語として読む前にひらがなに変換する
+    pub is_romaji_to_kana: bool,
+}
 
-pub struct EngToKana<'a> {
-    dict_data: RwLockReadGuard<'a, HashMap<String, String>>,
+pub struct EngToKana {
+    dict_data: Arc<TransDict>,
 }
 
-impl EngToKana<'_> {
-    pub async fn download_and_init_dic<P>(download_to_path: P) -> anyhow::Result<()>
+impl EngToKana {
+    /// bep-eng.dic がまだなければダウンロードしてから init_dic と同じように読み込む
+    pub async fn download_and_init_dic<P, Q>(
+        download_to_path: P,
+        overlay_path: Q,
+    ) -> anyhow::Result<DictLoadReport>
     where
         P: Into<PathBuf>,
+        Q: AsRef<Path>,
     {
         let download_to_path: PathBuf = download_to_path.into();
         let bepeng_dic_path = download_to_path.join("bep-eng.dic");
 
         // ダウンロードされていない場合のみダウンロードする
         if !bepeng_dic_path.exists() {
-            let response = reqwest::get(BEPENG_DIC_URL).await?;
+            let response = reqwest::get(BEPENG_DIC_URL).await?.error_for_status()?;
             let bytes = response.bytes().await?;
 
             let mut buffer = File::create(&bepeng_dic_path).await?;
             buffer.write_all(&bytes).await?;
         }
 
-        let mut bepeng_dic = File::open(bepeng_dic_path).await?;
+        Ok(Self::init_dic(download_to_path, overlay_path).await)
+    }
 
-        let mut dic_text = String::new();
-        bepeng_dic.read_to_string(&mut dic_text).await?;
+    /// 同梱の辞書、ダウンロード済みの bep-eng.dic、ユーザー辞書の順に読み込む
+    /// (後に読み込んだ読みを優先する、ダウンロードはしない)
+    pub async fn init_dic<P, Q>(download_to_path: P, overlay_path: Q) -> DictLoadReport
+    where
+        P: Into<PathBuf>,
+        Q: AsRef<Path>,
+    {
+        let bepeng_dic_path = download_to_path.into().join("bep-eng.dic");
+        let overlay_path = overlay_path.as_ref();
 
-        // 改行で区切って、空白行、コメント行を消す
-        let vec: Vec<&str> = dic_text
-            .split("\n")
-            .filter(|s| *s != "")
-            .filter(|s| !s.starts_with("#"))
-            .collect();
+        let mut trans_dict: HashMap<String, String> = HashMap::new();
+        let mut malformed = parse_dic(&mut trans_dict, BUNDLED_DIC, "bundled.dic");
 
-        let mut trans_dict: HashMap<String, String> = HashMap::with_capacity(vec.len());
+        // ユーザー辞書がなければ書き方だけ書いたファイルを作成する
+        if !overlay_path.exists() {
+            if let Err(err) = tokio::fs::write(overlay_path, OVERLAY_TEMPLATE).await {
+                log::warn!("Failed to create {}: {err}", overlay_path.display());
+            }
+        }
+
+        // 読み込めなかったファイルは飛ばす
+        for path in [bepeng_dic_path.as_path(), overlay_path] {
+            if !path.exists() {
+                continue;
+            }
+
+            match tokio::fs::read_to_string(path).await {
+                Ok(text) => {
+                    let source = path.display().to_string();
+                    malformed.extend(parse_dic(&mut trans_dict, &text, &source));
+                }
+                Err(err) => log::warn!("Failed to read {}: {err}", path.display()),
+            }
+        }
 
-        // それぞれの要素を空白で前半と後半で分割して、trans_dict に組み合わせを登録
-        vec.iter().for_each(|s| {
-            let split: Vec<&str> = s.split(" ").collect();
-            let before = split[0].to_string();
-            let after = split[1].to_string();
+        for line in &malformed {
+            log::warn!("Malformed line in engtokana dict: {line}");
+        }
 
-            trans_dict.insert(before, after);
-        });
+        let report = DictLoadReport {
+            entries: trans_dict.len(),
+            malformed,
+        };
 
         // static 変数を更新
+        let trans_dict = Arc::new(TransDict::new(trans_dict));
         {
-            let mut lock = TRANS_DICT_MAP.write().unwrap();
+            let mut lock = TRANS_DICT.write().unwrap();
             *lock = trans_dict
         }
 
-        Ok(())
+        report
+    }
+
+    /// 辞書にない単語や略語を1文字ずつ読む最大の長さを設定する (0 なら1文字ずつ読まない)
+    pub fn set_spell_max_len(max_len: usize) {
+        SPELL_MAX_LEN.store(max_len, Ordering::Relaxed);
+    }
+
+    /// 辞書にあっても1文字ずつ読む、大文字だけの単語 (略語) の最大の長さを設定する
+    /// (0 なら辞書を優先する)
+    pub fn set_acronym_max_len(max_len: usize) {
+        ACRONYM_MAX_LEN.store(max_len, Ordering::Relaxed);
     }
 
     fn new() -> Self {
-        let lock = TRANS_DICT_MAP.read().unwrap();
-        Self { dict_data: lock }
+        let dict_data = TRANS_DICT.read().unwrap().clone();
+        Self { dict_data }
     }
 
     pub fn convert_all(text: &str) -> String {
+        Self::convert_all_with(text, ConvertOptions::default())
+    }
+
+    pub fn convert_all_with(text: &str, options: ConvertOptions) -> String {
         let slf = Self::new();
         let mut result_words = vec![];
 
-        for i in slf.split_en_other(text) {
-            for splited in slf.split_word(i) {
-                result_words.push(slf.convert_single_word(splited));
+        let spell_max_len = SPELL_MAX_LEN.load(Ordering::Relaxed);
+        let acronym_max_len = ACRONYM_MAX_LEN.load(Ordering::Relaxed);
+        let texts = slf.split_en_other(text);
+
+        for (idx, i) in texts.iter().enumerate() {
+            // snake_case の _ は単語の区切りなので読まない
+            if is_word_separator(&texts, idx) {
+                continue;
+            }
+
+            if options.is_romaji_to_kana {
+                if let Some(hiragana) = slf.romaji_to_hiragana(i) {
+                    result_words.push(Cow::Owned(hiragana));
+                    continue;
+                }
+            }
+
+            for word in split_identifier(i) {
+                for splited in slf.split_word(word) {
+                    result_words.push(slf.convert_single_word(
+                        splited,
+                        spell_max_len,
+                        acronym_max_len,
+                    ));
+                }
             }
         }
 
@@ -112,69 +218,76 @@ impl EngToKana<'_> {
         result_texts
     }
 
-    fn convert_single_word<'a>(&'a self, word: &'a str) -> &'a str {
-        if !self.is_convert_target(word) {
-            return word;
+    // ローマ字らしければひらがなにして返す (英語の辞書にある単語はローマ字らしさを下げる)
+    fn romaji_to_hiragana(&self, word: &str) -> Option<String> {
+        // 大文字だけの単語は略語として扱う
+        if word.chars().all(|c| c.is_ascii_uppercase()) {
+            return None;
         }
 
-        let result = self
-            .dict_data
-            .get(&word.to_uppercase())
-            .map(|i| i.as_str())
-            .unwrap_or_else(|| word);
-        result
-    }
-
-    // 渡された単語が英語かつ、大文字だけではないなら True を返す
-    fn is_convert_target(&self, word: &str) -> bool {
-        // 英語かどうか
-        if word.is_ascii() {
-            //大文字だけかどうか
-            if word.chars().all(|c| c.is_uppercase()) {
-                false
-            } else {
-                true
-            }
-        } else {
-            false
-        }
+        let romaji = parse_romaji(word)?;
+        let score = match self.dict_data.readings.contains_key(&word.to_uppercase()) {
+            true => romaji.score - 0.5,
+            false => romaji.score,
+        };
+
+        (score >= ROMAJI_THRESHOLD).then_some(romaji.hiragana)
     }
 
-    fn split_word<'a>(&self, target_str: &'a str) -> Vec<&'a str> {
-        if !self.is_convert_target(target_str) {
-            return vec![&target_str]; // 変換したい文字ではない場合、そのまま返す
+    fn convert_single_word<'a>(
+        &'a self,
+        word: &'a str,
+        spell_max_len: usize,
+        acronym_max_len: usize,
+    ) -> Cow<'a, str> {
+        if word.is_empty() || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Cow::Borrowed(word);
         }
 
-        let target_str_upper = &target_str.to_uppercase();
-        if self.dict_data.contains_key(target_str_upper) {
-            return vec![&target_str]; // target_word が辞書にある場合そのまま返す
-        }
+        // 短い大文字だけの単語は辞書にあっても略語として1文字ずつ読む
+        let is_acronym = word.chars().all(|c| c.is_ascii_uppercase());
 
-        // target_word の1文字目から、文字数-1文字目, 文字数-2文字目, ... と見ていく
-        for i in (0..target_str.chars().count()).rev() {
-            let target_prefix = &target_str_upper[..i];
+        if !is_acronym || word.len() > acronym_max_len {
+            if let Some(reading) = self.dict_data.readings.get(&word.to_uppercase()) {
+                return Cow::Borrowed(reading);
+            }
+        }
 
-            // target_word の最初の方が辞書にあったら
-            if self.dict_data.contains_key(target_prefix) {
-                let target_suffix = &target_str[i..];
+        // 辞書になく、分割もできなかった単語
+        match word.len() <= spell_max_len {
+            true => Cow::Owned(spell_out(word)),
+            false => Cow::Borrowed(word),
+        }
+    }
 
-                // target_word から見つかった単語を引いて、まだ文字が残っているなら
-                if !target_suffix.is_empty() {
-                    let target_suffix_split = self.split_word(target_suffix); // さらに残りを再帰で分割する
+    // 辞書の単語に分割する対象か (大文字だけの単語は略語として分割せずに読む)
+    fn is_segment_target(&self, word: &str) -> bool {
+        word.is_ascii() && !word.chars().all(|c| c.is_ascii_uppercase())
+    }
 
-                    let mut vec = vec![&target_str[..i]];
-                    vec.extend(target_suffix_split);
-                    return vec;
-                } else {
-                    return vec![&target_str[..i]];
-                }
-            }
+    fn split_word<'a>(&self, target_str: &'a str) -> Vec<&'a str> {
+        if !self.is_segment_target(target_str) {
+            return vec![&target_str]; // 分割しない場合、そのまま返す
         }
 
-        return vec![&target_str]; // 分割できなかったら入力をそのまま返す
+        self.dict_data.segment(target_str)
     }
 }
 
+// 前後が英単語の _ だけの文字列か
+fn is_word_separator(texts: &[&str], idx: usize) -> bool {
+    let is_alphabetic = |i: usize| {
+        texts
+            .get(i)
+            .is_some_and(|s| s.starts_with(|c: char| c.is_ascii_alphabetic()))
+    };
+
+    texts[idx].chars().all(|c| c == '_')
+        && idx > 0
+        && is_alphabetic(idx - 1)
+        && is_alphabetic(idx + 1)
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Instant;
@@ -186,7 +299,7 @@ mod tests {
     #[tokio::test]
     async fn convert_all() -> anyhow::Result<()> {
         create_dir_all("./appdata").await?;
-        EngToKana::download_and_init_dic("./appdata").await?;
+        EngToKana::download_and_init_dic("./appdata", "./appdata/engtokana.dic").await?;
 
         let now = Instant::now();
         let result = EngToKana::convert_all("Hello");
@@ -229,10 +342,54 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_spell_unknown_words() -> anyhow::Result<()> {
+        // 辞書の内容によらない単語だけを確認する
+        let result = EngToKana::convert_all("GPUとAPI");
+        assert_eq!("ジーピーユーとエーピーアイ", result);
+
+        let result = EngToKana::convert_all("XQZXQZXQZ");
+        assert_eq!("XQZXQZXQZ", result);
+
+        let result = EngToKana::convert_all("get_GPU");
+        assert_eq!(EngToKana::convert_all("get") + "ジーピーユー", result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_acronym_max_len() -> anyhow::Result<()> {
+        // 辞書にある4文字の大文字だけの単語も、設定すれば1文字ずつ読む
+        EngToKana::set_acronym_max_len(4);
+        let result = EngToKana::convert_all("BOOK");
+        EngToKana::set_acronym_max_len(3);
+
+        assert_eq!("ビーオーオーケー", result);
+        assert_eq!("ブック", EngToKana::convert_all("BOOK"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_romaji_to_kana() -> anyhow::Result<()> {
+        let options = ConvertOptions {
+            is_romaji_to_kana: true,
+        };
+
+        let result = EngToKana::convert_all_with("arigatou yoroshiku!", options);
+        assert_eq!("ありがとう よろしく!", result);
+
+        // 設定しない場合はローマ字として読まない
+        let result = EngToKana::convert_all("arigatou");
+        assert_ne!("ありがとう", result);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_download_and_init_dic() -> anyhow::Result<()> {
         create_dir_all("./appdata").await?;
-        EngToKana::download_and_init_dic("./appdata").await?;
+        EngToKana::download_and_init_dic("./appdata", "./appdata/engtokana.dic").await?;
 
         let path = PathBuf::from("./appdata/bep-eng.dic");
         assert!(path.exists());
@@ -0,0 +1,280 @@
+// ローマ字として扱う最低のスコア
+pub(crate) const ROMAJI_THRESHOLD: f32 = 0.7;
+
+// (ローマ字, ひらがな) ヘボン式、訓令式と IME でよく使う綴り
+// 長いものから順に照合する
+const ROMAJI_TABLE: &[(&str, &str)] = &[
+    // 3文字
+    ("kya", "きゃ"),
+    ("kyu", "きゅ"),
+    ("kyo", "きょ"),
+    ("sha", "しゃ"),
+    ("shi", "し"),
+    ("shu", "しゅ"),
+    ("she", "しぇ"),
+    ("sho", "しょ"),
+    ("sya", "しゃ"),
+    ("syu", "しゅ"),
+    ("syo", "しょ"),
+    ("cha", "ちゃ"),
+    ("chi", "ち"),
+    ("chu", "ちゅ"),
+    ("che", "ちぇ"),
+    ("cho", "ちょ"),
+    ("tya", "ちゃ"),
+    ("tyu", "ちゅ"),
+    ("tyo", "ちょ"),
+    ("tsu", "つ"),
+    ("nya", "にゃ"),
+    ("nyu", "にゅ"),
+    ("nyo", "にょ"),
+    ("hya", "ひゃ"),
+    ("hyu", "ひゅ"),
+    ("hyo", "ひょ"),
+    ("mya", "みゃ"),
+    ("myu", "みゅ"),
+    ("myo", "みょ"),
+    ("rya", "りゃ"),
+    ("ryu", "りゅ"),
+    ("ryo", "りょ"),
+    ("gya", "ぎゃ"),
+    ("gyu", "ぎゅ"),
+    ("gyo", "ぎょ"),
+    ("zya", "じゃ"),
+    ("zyu", "じゅ"),
+    ("zyo", "じょ"),
+    ("jya", "じゃ"),
+    ("jyu", "じゅ"),
+    ("jyo", "じょ"),
+    ("dya", "ぢゃ"),
+    ("dyu", "ぢゅ"),
+    ("dyo", "ぢょ"),
+    ("bya", "びゃ"),
+    ("byu", "びゅ"),
+    ("byo", "びょ"),
+    ("pya", "ぴゃ"),
+    ("pyu", "ぴゅ"),
+    ("pyo", "ぴょ"),
+    // 2文字
+    ("ka", "か"),
+    ("ki", "き"),
+    ("ku", "く"),
+    ("ke", "け"),
+    ("ko", "こ"),
+    ("sa", "さ"),
+    ("si", "し"),
+    ("su", "す"),
+    ("se", "せ"),
+    ("so", "そ"),
+    ("ta", "た"),
+    ("ti", "ち"),
+    ("tu", "つ"),
+    ("te", "て"),
+    ("to", "と"),
+    ("na", "な"),
+    ("ni", "に"),
+    ("nu", "ぬ"),
+    ("ne", "ね"),
+    ("no", "の"),
+    ("ha", "は"),
+    ("hi", "ひ"),
+    ("hu", "ふ"),
+    ("fu", "ふ"),
+    ("he", "へ"),
+    ("ho", "ほ"),
+    ("fa", "ふぁ"),
+    ("fi", "ふぃ"),
+    ("fe", "ふぇ"),
+    ("fo", "ふぉ"),
+    ("ma", "ま"),
+    ("mi", "み"),
+    ("mu", "む"),
+    ("me", "め"),
+    ("mo", "も"),
+    ("ya", "や"),
+    ("yu", "ゆ"),
+    ("yo", "よ"),
+    ("ra", "ら"),
+    ("ri", "り"),
+    ("ru", "る"),
+    ("re", "れ"),
+    ("ro", "ろ"),
+    ("wa", "わ"),
+    ("wo", "を"),
+    ("ga", "が"),
+    ("gi", "ぎ"),
+    ("gu", "ぐ"),
+    ("ge", "げ"),
+    ("go", "ご"),
+    ("za", "ざ"),
+    ("zi", "じ"),
+    ("zu", "ず"),
+    ("ze", "ぜ"),
+    ("zo", "ぞ"),
+    ("ja", "じゃ"),
+    ("ji", "じ"),
+    ("ju", "じゅ"),
+    ("je", "じぇ"),
+    ("jo", "じょ"),
+    ("da", "だ"),
+    ("di", "ぢ"),
+    ("du", "づ"),
+    ("de", "で"),
+    ("do", "ど"),
+    ("ba", "ば"),
+    ("bi", "び"),
+    ("bu", "ぶ"),
+    ("be", "べ"),
+    ("bo", "ぼ"),
+    ("pa", "ぱ"),
+    ("pi", "ぴ"),
+    ("pu", "ぷ"),
+    ("pe", "ぺ"),
+    ("po", "ぽ"),
+    // 1文字
+    ("a", "あ"),
+    ("i", "い"),
+    ("u", "う"),
+    ("e", "え"),
+    ("o", "お"),
+];
+
+// ローマ字らしさを上げる綴り (英語ではあまり使わない)
+const ROMAJI_MARKERS: &[&str] = &[
+    "shi", "chi", "tsu", "fu", "j", "ky", "sh", "ch", "ny", "hy", "my", "ry", "gy", "by", "py",
+];
+
+// ローマ字らしさを上げる母音の並び (長音など)
+const VOWEL_MARKERS: &[&str] = &["ou", "uu", "ii", "ei", "ai", "oi"];
+
+/// ローマ字をひらがなに変換した結果
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Romaji {
+    pub(crate) hiragana: String,
+
+    /// ローマ字らしさ (0.0 から 1.0)
+    pub(crate) score: f32,
+}
+
+/// word をローマ字として読んだ場合のひらがなとスコアを返す
+/// (ローマ字として読めない場合は None)
+pub(crate) fn parse_romaji(word: &str) -> Option<Romaji> {
+    let word = word.to_ascii_lowercase();
+    let bytes = word.as_bytes();
+
+    if !word.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+
+    let mut hiragana = String::with_capacity(word.len() * 3);
+    let mut morae = 0;
+    let mut markers = 0;
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        let rest = &word[idx..];
+        let (c, next) = (bytes[idx], bytes.get(idx + 1).copied());
+
+        // 同じ子音が続く場合 (tch も含む) は促音
+        let is_sokuon = next.is_some_and(|next| {
+            c != b'n' && !is_vowel(c) && (next == c || (c == b't' && rest.starts_with("tch")))
+        });
+
+        if is_sokuon {
+            hiragana.push('っ');
+            morae += 1;
+            markers += 1;
+            idx += 1;
+            continue;
+        }
+
+        // 母音と y 以外の前か、最後の n は撥音
+        if c == b'n' && !next.is_some_and(|next| is_vowel(next) || next == b'y') {
+            hiragana.push('ん');
+            morae += 1;
+
+            // nn は「ん」の後に な行が続く場合以外は1文字の「ん」
+            let after_nn = bytes.get(idx + 2).copied();
+            let is_double_n =
+                next == Some(b'n') && !after_nn.is_some_and(|c| is_vowel(c) || c == b'y');
+
+            idx += if is_double_n { 2 } else { 1 };
+            if next.is_some() {
+                markers += 1;
+            }
+            continue;
+        }
+
+        let (romaji, kana) = ROMAJI_TABLE
+            .iter()
+            .find(|(romaji, _)| rest.starts_with(romaji))?;
+
+        if ROMAJI_MARKERS
+            .iter()
+            .any(|marker| romaji.starts_with(marker))
+        {
+            markers += 1;
+        }
+
+        hiragana.push_str(kana);
+        morae += 1;
+        idx += romaji.len();
+    }
+
+    markers += VOWEL_MARKERS
+        .iter()
+        .map(|marker| word.matches(marker).count())
+        .sum::<usize>();
+
+    let score = (0.3 + 0.1 * morae as f32 + 0.2 * markers as f32).min(1.0);
+    Some(Romaji { hiragana, score })
+}
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'i' | b'u' | b'e' | b'o')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hiragana(word: &str) -> Option<String> {
+        parse_romaji(word).map(|romaji| romaji.hiragana)
+    }
+
+    fn is_romaji(word: &str) -> bool {
+        parse_romaji(word).is_some_and(|romaji| romaji.score >= ROMAJI_THRESHOLD)
+    }
+
+    #[test]
+    fn test_parse_romaji() {
+        assert_eq!(hiragana("arigatou").as_deref(), Some("ありがとう"));
+        assert_eq!(hiragana("yoroshiku").as_deref(), Some("よろしく"));
+        assert_eq!(hiragana("Konnichiwa").as_deref(), Some("こんにちわ"));
+        assert_eq!(hiragana("konnnichiha").as_deref(), Some("こんにちは"));
+        assert_eq!(hiragana("kitte").as_deref(), Some("きって"));
+        assert_eq!(hiragana("matcha").as_deref(), Some("まっちゃ"));
+        assert_eq!(hiragana("sinbun").as_deref(), Some("しんぶん"));
+        assert_eq!(hiragana("tyotto").as_deref(), Some("ちょっと"));
+        assert_eq!(hiragana("hello"), None);
+        assert_eq!(hiragana("street"), None);
+    }
+
+    #[test]
+    fn test_romaji_score() {
+        for word in [
+            "arigatou",
+            "yoroshiku",
+            "konnichiwa",
+            "oyasumi",
+            "sugoi",
+            "kanji",
+        ] {
+            assert!(is_romaji(word), "{word}");
+        }
+
+        for word in ["time", "make", "game", "potato", "hello", "a"] {
+            assert!(!is_romaji(word), "{word}");
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use aho_corasick::{AhoCorasick, MatchKind};
+
+/// 読み込んだ辞書と、単語の分割に使うオートマトン
+pub(crate) struct TransDict {
+    /// 大文字の英単語 -> 読み
+    pub(crate) readings: HashMap<String, String>,
+
+    /// アルファベットだけの単語を大文字と小文字を区別せずに探す
+    /// (作成できなかった場合は None で、分割しない)
+    matcher: Option<AhoCorasick>,
+}
+
+/// 分割の途中結果
+struct Step {
+    /// (辞書にない文字数, 単語数)
+    cost: (usize, usize),
+
+    /// 最後の区切りの位置
+    start: usize,
+    is_word: bool,
+}
+
+impl TransDict {
+    pub(crate) fn new(readings: HashMap<String, String>) -> TransDict {
+        let words = readings
+            .keys()
+            .filter(|word| !word.is_empty() && word.chars().all(|c| c.is_ascii_alphabetic()));
+
+        // 重なった一致も全て取得するので MatchKind::Standard にする
+        let matcher = AhoCorasick::builder()
+            .ascii_case_insensitive(true)
+            .match_kind(MatchKind::Standard)
+            .build(words)
+            .inspect_err(|err| log::error!("Failed to build engtokana matcher: {err}"))
+            .ok();
+
+        TransDict { readings, matcher }
+    }
+
+    /// word を辞書の単語に分割する
+    /// (辞書にない文字が最も少なく、次に単語の数が最も少ない分け方を選ぶ)
+    ///
+    /// 辞書にない文字が続く部分は1つにまとめる
+    /// かかる時間は word の長さと辞書の単語の最大の長さの積に比例する
+    pub(crate) fn segment<'a>(&self, word: &'a str) -> Vec<&'a str> {
+        // 1バイトを1文字として扱うので ASCII のみ分割する
+        let Some(matcher) = self.matcher.as_ref().filter(|_| word.is_ascii()) else {
+            return vec![word];
+        };
+
+        // word に含まれる辞書の単語を終わりの位置の順に並べる
+        let mut matches: Vec<(usize, usize)> = matcher
+            .find_overlapping_iter(word)
+            .map(|m| (m.start(), m.end()))
+            .collect();
+
+        if matches.is_empty() {
+            return vec![word];
+        }
+        matches.sort_unstable_by_key(|(_, end)| *end);
+
+        // best[i]: word[..i] を最も良く分割したときの最後の区切り
+        let mut best: Vec<Step> = Vec::with_capacity(word.len() + 1);
+        best.push(Step {
+            cost: (0, 0),
+            start: 0,
+            is_word: false,
+        });
+
+        let mut matches = matches.into_iter().peekable();
+
+        for end in 1..=word.len() {
+            // 1文字を辞書にない文字として扱う場合
+            let (unknown, pieces) = best[end - 1].cost;
+            let mut step = Step {
+                cost: (unknown + 1, pieces),
+                start: end - 1,
+                is_word: false,
+            };
+
+            // end で終わる辞書の単語を使う場合
+            while let Some((start, _)) = matches.next_if(|(_, match_end)| *match_end == end) {
+                let (unknown, pieces) = best[start].cost;
+                let cost = (unknown, pieces + 1);
+
+                if cost < step.cost {
+                    step = Step {
+                        cost,
+                        start,
+                        is_word: true,
+                    };
+                }
+            }
+
+            best.push(step);
+        }
+
+        // 後ろからたどって分割する (辞書にない文字が続く部分はまとめる)
+        let mut result = vec![];
+        let mut end = word.len();
+        let mut unknown_end = None;
+
+        while end > 0 {
+            let step = &best[end];
+
+            match step.is_word {
+                true => {
+                    if let Some(unknown_end) = unknown_end.take() {
+                        result.push(&word[end..unknown_end]);
+                    }
+                    result.push(&word[step.start..end]);
+                }
+                false => {
+                    unknown_end.get_or_insert(end);
+                }
+            }
+
+            end = step.start;
+        }
+
+        if let Some(unknown_end) = unknown_end {
+            result.push(&word[..unknown_end]);
+        }
+
+        result.reverse();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    fn trans_dict(words: &[&str]) -> TransDict {
+        let readings = words
+            .iter()
+            .map(|word| (word.to_string(), String::new()))
+            .collect();
+
+        TransDict::new(readings)
+    }
+
+    #[test]
+    fn test_segment() {
+        let dict = trans_dict(&["VERY", "EXCELLENT", "HELLO", "AB", "ABC", "CD"]);
+
+        assert_eq!(
+            dict.segment("veryveryexcellent"),
+            vec!["very", "very", "excellent"]
+        );
+        assert_eq!(dict.segment("HelloXYZhello"), vec!["Hello", "XYZ", "hello"]);
+        assert_eq!(dict.segment("xyz"), vec!["xyz"]);
+
+        // 先頭から一番長い単語を選ぶと "ABC" + "D" になる
+        assert_eq!(dict.segment("abcd"), vec!["ab", "cd"]);
+    }
+
+    fn long_inputs() -> [String; 3] {
+        [
+            "a".repeat(100_000),
+            "ab".repeat(50_000),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+                .chars()
+                .filter(|c| c.is_ascii_alphabetic())
+                .collect::<String>()
+                .repeat(5_000),
+        ]
+    }
+
+    #[test]
+    fn test_segment_long_input() {
+        let dict = trans_dict(&["A", "AA", "AAA", "AB", "FACE"]);
+
+        // 長い入力でも元の文字列を失わずに分割する
+        for input in long_inputs() {
+            let result = dict.segment(&input);
+            assert_eq!(result.concat(), input);
+        }
+    }
+
+    // 時間を測るので通常のテストでは実行しない (cargo test -- --ignored で実行する)
+    #[ignore]
+    #[test]
+    fn bench_segment() {
+        let dict = trans_dict(&[
+            "A", "AA", "AAA", "AAAA", "AB", "BA", "E", "F", "FACE", "DEAD",
+        ]);
+
+        // 同じ文字が続く場合や、ハッシュ値のような文字列でも長さに比例した時間で終わる
+        for input in long_inputs() {
+            let now = Instant::now();
+            let result = dict.segment(&input);
+            let elapsed = now.elapsed();
+            dbg!(input.len(), result.len(), elapsed);
+
+            assert_eq!(result.concat(), input);
+            assert!(elapsed.as_secs() < 5);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+/// アルファベット1文字ずつの読み (A から Z の順)
+const LETTER_READINGS: [&str; 26] = [
+    "エー",
+    "ビー",
+    "シー",
+    "ディー",
+    "イー",
+    "エフ",
+    "ジー",
+    "エイチ",
+    "アイ",
+    "ジェー",
+    "ケー",
+    "エル",
+    "エム",
+    "エヌ",
+    "オー",
+    "ピー",
+    "キュー",
+    "アール",
+    "エス",
+    "ティー",
+    "ユー",
+    "ブイ",
+    "ダブリュー",
+    "エックス",
+    "ワイ",
+    "ゼット",
+];
+
+/// 英単語を1文字ずつカタカナで読む ("GPU" -> "ジーピーユー")
+/// (アルファベット以外の文字はそのまま残す)
+pub(crate) fn spell_out(word: &str) -> String {
+    word.chars()
+        .map(|c| match c.is_ascii_alphabetic() {
+            true => LETTER_READINGS[(c.to_ascii_uppercase() as u8 - b'A') as usize],
+            false => "",
+        })
+        .collect()
+}
+
+/// camelCase などの識別子を単語に分割する ("HTTPServer" -> ["HTTP", "Server"])
+pub(crate) fn split_identifier(word: &str) -> Vec<&str> {
+    let chars: Vec<char> = word.chars().collect();
+
+    if !word.is_ascii() || !chars.iter().all(|c| c.is_ascii_alphabetic()) {
+        return vec![word];
+    }
+
+    let mut result = vec![];
+    let mut range_start = 0;
+
+    for i in 1..chars.len() {
+        let (prev, cur) = (chars[i - 1], chars[i]);
+        let next_is_lower = chars.get(i + 1).is_some_and(|c| c.is_ascii_lowercase());
+
+        // 小文字の後の大文字 (camelCase) か、大文字が続いた後の大文字 + 小文字 (HTTPServer) で区切る
+        let is_boundary = (prev.is_ascii_lowercase() && cur.is_ascii_uppercase())
+            || (prev.is_ascii_uppercase() && cur.is_ascii_uppercase() && next_is_lower);
+
+        if is_boundary {
+            result.push(&word[range_start..i]);
+            range_start = i;
+        }
+    }
+
+    result.push(&word[range_start..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spell_out() {
+        assert_eq!(spell_out("GPU"), "ジーピーユー");
+        assert_eq!(spell_out("api"), "エーピーアイ");
+    }
+
+    #[test]
+    fn test_split_identifier() {
+        assert_eq!(split_identifier("camelCase"), vec!["camel", "Case"]);
+        assert_eq!(split_identifier("HTTPServer"), vec!["HTTP", "Server"]);
+        assert_eq!(split_identifier("getGPUInfo"), vec!["get", "GPU", "Info"]);
+        assert_eq!(split_identifier("Hello"), vec!["Hello"]);
+        assert_eq!(split_identifier("API"), vec!["API"]);
+    }
+}
@@ -5,10 +5,10 @@ use serenity::all::{
     ChannelId, CommandOptionType, Context, CreateCommand, CreateCommandOption, CreateEmbed,
     CreateEmbedFooter, GuildId, UserId,
 };
-use sonorust_db::{GuildData, GuildDataMut};
+use sonorust_db::{GuildData, GuildDataMut, Permission};
 
 use crate::{
-    _langrustang_autogen::Lang, crate_extensions::serenity::SerenityHttpExt as _,
+    _langrustang_autogen::Lang, crate_extensions::serenity::SerenityContextExt as _,
     errors::SonorustError,
 };
 
@@ -107,24 +107,13 @@ async fn autojoin_setting(
     text_ch_id: ChannelId,
 ) -> Result<(Option<CreateEmbed>, Option<&str>), SonorustError> {
     let guild_id = option_guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
-    let member = guild_id.member(&ctx.http, user_id).await?;
+    let is_permitted = ctx
+        .has_permission(guild_id, user_id, Permission::ManageServer)
+        .await?;
 
-    let is_bot_owner = {
-        let app_owner_id = ctx.http.get_bot_owner_id().await;
-        app_owner_id == user_id
-    };
-
-    let is_admin = {
-        #[allow(deprecated)]
-        match member.permissions(&ctx.cache) {
-            Ok(permissons) => permissons.administrator(),
-            Err(_) => false,
-        }
-    };
-
-    // 管理者でもbotの所有者でもなければ
-    if !is_admin && !is_bot_owner {
-        return Ok((None, Some(lang_t!("msg.only_admin", lang))));
+    // サーバー設定を変更する権限がなければ
+    if !is_permitted {
+        return Ok((None, Some(lang_t!("msg.no_permission", lang))));
     }
 
     let text = {
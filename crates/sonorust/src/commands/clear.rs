@@ -1,9 +1,12 @@
 use langrustang::lang_t;
 use serenity::all::{Context, CreateCommand, GuildId, UserId};
+use sonorust_db::Permission;
 
 use crate::{
     _langrustang_autogen::Lang,
-    crate_extensions::{rwlock::RwLockExt, sonorust_setting::SettingJsonExt},
+    crate_extensions::{
+        rwlock::RwLockExt, serenity::SerenityContextExt, sonorust_setting::SettingJsonExt,
+    },
     errors::SonorustError,
     Handler,
 };
@@ -30,6 +33,14 @@ pub async fn clear(
         return Ok(lang_t!("clear.user_notconnect", lang));
     }
 
+    // /clear を使う権限がない場合
+    if !ctx
+        .has_permission(guild_id, user_id, Permission::Clear)
+        .await?
+    {
+        return Ok(lang_t!("msg.no_permission", lang));
+    }
+
     // キューをクリア
     handler.channel_queues.with_write(|lock| {
         let queue = lock.get_mut(&guild_id);
@@ -39,6 +50,11 @@ pub async fn clear(
         }
     });
 
+    // 音声を生成中のメッセージも読み上げないようにする
+    handler
+        .pending_messages
+        .with_write(|lock| lock.remove(&guild_id));
+
     let manager = songbird::get(ctx).await.unwrap();
     if let Some(handler_lock) = manager.get(guild_id) {
         let mut handler = handler_lock.lock().await;
@@ -0,0 +1,28 @@
+use langrustang::format_t;
+use serenity::all::{CreateAttachment, GuildId};
+use sonorust_db::GuildData;
+
+use crate::{_langrustang_autogen::Lang, errors::SonorustError};
+
+use super::format::DictFormat;
+
+/// サーバー辞書をファイルとして書き出す
+pub async fn dict_export(
+    guild_id: Option<GuildId>,
+    format: DictFormat,
+    lang: Lang,
+) -> Result<(CreateAttachment, String), SonorustError> {
+    let guild_id = guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
+    let guilddata = GuildData::from(guild_id).await?;
+
+    let exported = format.export(&guilddata.dict);
+    let attachment = CreateAttachment::bytes(exported.value, format.file_name());
+
+    let count = guilddata.dict.len() - exported.skipped;
+    let content = match exported.skipped {
+        0 => format_t!("dict.export.done", lang, count),
+        skipped => format_t!("dict.export.done_skipped", lang, count, skipped),
+    };
+
+    Ok((attachment, content))
+}
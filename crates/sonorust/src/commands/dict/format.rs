@@ -0,0 +1,509 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+use sonorust_db::{DictEntry, MatchMode};
+
+/// 辞書ファイルの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictFormat {
+    /// [{"word": 単語, "reading": 読み方, "mode": 一致方法, "priority": 優先度}]
+    Json,
+    /// word,reading,mode,priority
+    Csv,
+    /// VOICEVOX のユーザー辞書
+    Voicevox,
+    /// {単語: 読み方} (多くの読み上げ bot で使われている形式)
+    TtsBot,
+    /// 単語<TAB>読み方
+    Tsv,
+}
+
+/// 読み込み、書き出しで使われなかった単語の数など
+#[derive(Debug)]
+pub struct Converted<T> {
+    pub value: T,
+    pub skipped: usize,
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    Json(serde_json::Error),
+    UnknownJson,
+    /// 閉じられていない " がある行
+    Csv(usize),
+}
+
+/// 読み込んだ単語 (一致方法などの確認は呼び出し側で行う)
+#[derive(Debug, Clone)]
+pub struct RawEntry {
+    pub word: String,
+    pub reading: String,
+    pub mode: String,
+    pub priority: String,
+}
+
+impl DictFormat {
+    pub const ALL: [DictFormat; 5] = [
+        DictFormat::Json,
+        DictFormat::Csv,
+        DictFormat::Voicevox,
+        DictFormat::TtsBot,
+        DictFormat::Tsv,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DictFormat::Json => "json",
+            DictFormat::Csv => "csv",
+            DictFormat::Voicevox => "voicevox",
+            DictFormat::TtsBot => "ttsbot",
+            DictFormat::Tsv => "tsv",
+        }
+    }
+
+    pub fn from_name(s: &str) -> Option<DictFormat> {
+        Self::ALL.into_iter().find(|format| format.as_str() == s)
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            DictFormat::Json => "dict.json",
+            DictFormat::Csv => "dict.csv",
+            DictFormat::Voicevox => "voicevox_dict.json",
+            DictFormat::TtsBot => "ttsbot_dict.json",
+            DictFormat::Tsv => "dict.tsv",
+        }
+    }
+
+    /// ファイル名と内容から形式を推測する
+    pub fn detect(file_name: &str, text: &str) -> Option<DictFormat> {
+        let extension = file_name
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_lowercase());
+
+        match extension.as_deref() {
+            Some("csv") => return Some(DictFormat::Csv),
+            Some("tsv") | Some("txt") => return Some(DictFormat::Tsv),
+            _ => (),
+        }
+
+        let value: Value = serde_json::from_str(text).ok()?;
+
+        match value {
+            Value::Array(_) => Some(DictFormat::Json),
+            Value::Object(map) => match map.values().next() {
+                Some(Value::Object(word)) if word.contains_key("surface") => {
+                    Some(DictFormat::Voicevox)
+                }
+                Some(Value::String(_)) | None => Some(DictFormat::TtsBot),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub fn parse(&self, text: &str) -> Result<Converted<Vec<RawEntry>>, ParseError> {
+        let text = text.trim_start_matches('\u{FEFF}');
+
+        match self {
+            DictFormat::Json => parse_json(text),
+            DictFormat::Csv => parse_csv(text),
+            DictFormat::Voicevox => parse_voicevox(text),
+            DictFormat::TtsBot => parse_ttsbot(text),
+            DictFormat::Tsv => Ok(parse_tsv(text)),
+        }
+    }
+
+    /// 単語順に並べて書き出す (形式で表せない単語は書き出さない)
+    pub fn export(&self, dict: &HashMap<String, DictEntry>) -> Converted<String> {
+        let mut entries: Vec<_> = dict.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        match self {
+            DictFormat::Json => export_json(&entries),
+            DictFormat::Csv => export_csv(&entries),
+            DictFormat::Voicevox => export_voicevox(&entries),
+            DictFormat::TtsBot => export_ttsbot(&entries),
+            DictFormat::Tsv => export_tsv(&entries),
+        }
+    }
+}
+
+fn raw_entry(word: &str, reading: &str) -> RawEntry {
+    RawEntry {
+        word: word.to_string(),
+        reading: reading.to_string(),
+        mode: String::new(),
+        priority: String::new(),
+    }
+}
+
+/// 正規表現は一致方法を表せない形式では書き出さない
+fn is_literal_like(entry: &DictEntry) -> bool {
+    entry.mode != MatchMode::Regex
+}
+
+fn to_pretty_json(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+fn parse_json(text: &str) -> Result<Converted<Vec<RawEntry>>, ParseError> {
+    let value: Value = serde_json::from_str(text).map_err(ParseError::Json)?;
+    let Value::Array(array) = value else {
+        return Err(ParseError::UnknownJson);
+    };
+
+    let mut skipped = 0;
+    let mut entries = vec![];
+
+    for item in array {
+        let field = |key: &str| match item.get(key) {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
+
+        let (Some(word), Some(reading)) = (field("word"), field("reading")) else {
+            skipped += 1;
+            continue;
+        };
+
+        entries.push(RawEntry {
+            word,
+            reading,
+            mode: field("mode").unwrap_or_default(),
+            priority: field("priority").unwrap_or_default(),
+        });
+    }
+
+    Ok(Converted {
+        value: entries,
+        skipped,
+    })
+}
+
+fn export_json(entries: &[(&String, &DictEntry)]) -> Converted<String> {
+    let array: Vec<_> = entries
+        .iter()
+        .map(|(word, entry)| {
+            json!({
+                "word": word,
+                "reading": entry.after_text,
+                "mode": entry.mode.as_str(),
+                "priority": entry.priority,
+            })
+        })
+        .collect();
+
+    Converted {
+        value: to_pretty_json(&Value::Array(array)),
+        skipped: 0,
+    }
+}
+
+fn parse_csv(text: &str) -> Result<Converted<Vec<RawEntry>>, ParseError> {
+    let mut skipped = 0;
+    let mut entries = vec![];
+
+    for (idx, record) in split_csv(text)?.into_iter().enumerate() {
+        // 1 行目がヘッダーなら飛ばす
+        if idx == 0
+            && record
+                .first()
+                .is_some_and(|s| s.eq_ignore_ascii_case("word"))
+        {
+            continue;
+        }
+
+        if record.iter().all(|s| s.is_empty()) {
+            continue;
+        }
+
+        let field = |i: usize| record.get(i).cloned().unwrap_or_default();
+        if record.len() < 2 {
+            skipped += 1;
+            continue;
+        }
+
+        entries.push(RawEntry {
+            word: field(0),
+            reading: field(1),
+            mode: field(2),
+            priority: field(3),
+        });
+    }
+
+    Ok(Converted {
+        value: entries,
+        skipped,
+    })
+}
+
+/// CSV を行ごとのフィールドに分ける (" で囲まれたフィールドに対応)
+fn split_csv(text: &str) -> Result<Vec<Vec<String>>, ParseError> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut quote_start_line = 1;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (in_quotes, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => in_quotes = false,
+            (false, '"') if field.is_empty() => {
+                in_quotes = true;
+                quote_start_line = line;
+            }
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') => (),
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                line += 1;
+            }
+            (_, c) => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(ParseError::Csv(quote_start_line));
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+fn export_csv(entries: &[(&String, &DictEntry)]) -> Converted<String> {
+    let escape = |s: &str| match s.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    };
+
+    let mut csv = String::from("word,reading,mode,priority\n");
+    for (word, entry) in entries {
+        csv += &format!(
+            "{},{},{},{}\n",
+            escape(word),
+            escape(&entry.after_text),
+            entry.mode.as_str(),
+            entry.priority
+        );
+    }
+
+    Converted {
+        value: csv,
+        skipped: 0,
+    }
+}
+
+fn parse_voicevox(text: &str) -> Result<Converted<Vec<RawEntry>>, ParseError> {
+    let value: Value = serde_json::from_str(text).map_err(ParseError::Json)?;
+    let Value::Object(map) = value else {
+        return Err(ParseError::UnknownJson);
+    };
+
+    let mut skipped = 0;
+    let mut entries = vec![];
+
+    for word in map.values() {
+        let surface = word.get("surface").and_then(Value::as_str);
+        let pronunciation = word.get("pronunciation").and_then(Value::as_str);
+
+        let (Some(surface), Some(pronunciation)) = (surface, pronunciation) else {
+            skipped += 1;
+            continue;
+        };
+
+        // VOICEVOX の優先度は 0 ~ 10 (標準は 5)
+        let priority = word
+            .get("priority")
+            .and_then(Value::as_i64)
+            .map(|priority| priority - 5)
+            .unwrap_or_default();
+
+        entries.push(RawEntry {
+            word: to_halfwidth(surface),
+            reading: pronunciation.to_string(),
+            mode: String::new(),
+            priority: priority.to_string(),
+        });
+    }
+
+    Ok(Converted {
+        value: entries,
+        skipped,
+    })
+}
+
+fn export_voicevox(entries: &[(&String, &DictEntry)]) -> Converted<String> {
+    let mut skipped = 0;
+    let mut map = Map::new();
+
+    for (word, entry) in entries {
+        // 読み方はカタカナのみ使える
+        let pronunciation = to_katakana(&entry.after_text);
+        let is_katakana = pronunciation
+            .chars()
+            .all(|c| matches!(c, 'ァ'..='ヴ' | 'ー'));
+
+        if !is_literal_like(entry) || pronunciation.is_empty() || !is_katakana {
+            skipped += 1;
+            continue;
+        }
+
+        let word = json!({
+            "surface": to_fullwidth(word),
+            "priority": (entry.priority + 5).clamp(0, 10),
+            "context_id": 1348,
+            "part_of_speech": "名詞",
+            "part_of_speech_detail_1": "固有名詞",
+            "part_of_speech_detail_2": "一般",
+            "part_of_speech_detail_3": "*",
+            "inflectional_type": "*",
+            "inflectional_form": "*",
+            "stem": "*",
+            "yomi": pronunciation,
+            "pronunciation": pronunciation,
+            "accent_type": 0,
+            "mora_count": null,
+            "accent_associative_rule": "*",
+        });
+
+        map.insert(uuid::Uuid::new_v4().to_string(), word);
+    }
+
+    Converted {
+        value: to_pretty_json(&Value::Object(map)),
+        skipped,
+    }
+}
+
+fn parse_ttsbot(text: &str) -> Result<Converted<Vec<RawEntry>>, ParseError> {
+    let value: Value = serde_json::from_str(text).map_err(ParseError::Json)?;
+    let Value::Object(map) = value else {
+        return Err(ParseError::UnknownJson);
+    };
+
+    let mut skipped = 0;
+    let mut entries = vec![];
+
+    for (word, reading) in map {
+        match reading {
+            Value::String(reading) => entries.push(raw_entry(&word, &reading)),
+            _ => skipped += 1,
+        }
+    }
+
+    Ok(Converted {
+        value: entries,
+        skipped,
+    })
+}
+
+fn export_ttsbot(entries: &[(&String, &DictEntry)]) -> Converted<String> {
+    let mut skipped = 0;
+    let mut map = Map::new();
+
+    for (word, entry) in entries {
+        match is_literal_like(entry) {
+            true => {
+                map.insert(word.to_string(), Value::String(entry.after_text.clone()));
+            }
+            false => skipped += 1,
+        }
+    }
+
+    Converted {
+        value: to_pretty_json(&Value::Object(map)),
+        skipped,
+    }
+}
+
+fn parse_tsv(text: &str) -> Converted<Vec<RawEntry>> {
+    let mut skipped = 0;
+    let mut entries = vec![];
+
+    for line in text.lines() {
+        // 空行と # から始まる行は無視
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match line.split_once('\t') {
+            Some((word, reading)) => entries.push(raw_entry(word, reading.trim_end())),
+            None => skipped += 1,
+        }
+    }
+
+    Converted {
+        value: entries,
+        skipped,
+    }
+}
+
+fn export_tsv(entries: &[(&String, &DictEntry)]) -> Converted<String> {
+    let mut skipped = 0;
+    let mut tsv = String::new();
+
+    for (word, entry) in entries {
+        // タブや改行を含む単語は表せない
+        let is_valid = !word.contains(['\t', '\n']) && !entry.after_text.contains(['\t', '\n']);
+
+        match is_literal_like(entry) && is_valid {
+            true => tsv += &format!("{word}\t{}\n", entry.after_text),
+            false => skipped += 1,
+        }
+    }
+
+    Converted {
+        value: tsv,
+        skipped,
+    }
+}
+
+/// 全角英数字と記号を半角にする
+fn to_halfwidth(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            '\u{3000}' => ' ',
+            _ => c,
+        })
+        .collect()
+}
+
+/// 半角英数字と記号を全角にする (VOICEVOX の辞書は全角で登録する)
+fn to_fullwidth(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '!'..='~' => char::from_u32(c as u32 + 0xFEE0).unwrap_or(c),
+            ' ' => '\u{3000}',
+            _ => c,
+        })
+        .collect()
+}
+
+/// ひらがなをカタカナにする
+fn to_katakana(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
@@ -0,0 +1,97 @@
+use langrustang::{format_t, lang_t};
+use serenity::all::{
+    Context, CreateActionRow, CreateEmbed, CreateSelectMenu, CreateSelectMenuKind,
+    CreateSelectMenuOption, GuildId,
+};
+use sonorust_db::{DictChange, DictEntry};
+
+use crate::{_langrustang_autogen::Lang, errors::SonorustError};
+
+use super::{reading_text, truncate};
+
+/// 表示する変更の数 (セレクトメニューに入れられる上限以下)
+const HISTORY_LIMIT: u32 = 20;
+
+/// サーバー辞書の最近の変更と、変更を元に戻すためのセレクトメニュー
+pub async fn dict_history(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    lang: Lang,
+) -> Result<(CreateEmbed, Vec<CreateActionRow>), SonorustError> {
+    let guild_id = guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    let changes = DictChange::list(guild_id, HISTORY_LIMIT).await?;
+    let guild_name = guild_id
+        .name(&ctx.cache)
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let embed = {
+        let description = match changes.is_empty() {
+            true => lang_t!("dict.history.empty", lang).to_string(),
+            false => changes
+                .iter()
+                .map(|change| {
+                    let user = match change.user_id {
+                        Some(user_id) => format!("<@{user_id}>"),
+                        None => lang_t!("dict.history.unknown_user", lang).to_string(),
+                    };
+
+                    format_t!(
+                        "dict.history.line",
+                        lang,
+                        change.id,
+                        change.changed_at,
+                        user,
+                        truncate(&change.word, 60),
+                        entry_text(change.old.as_ref(), lang),
+                        entry_text(change.new.as_ref(), lang)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+
+        let title = format_t!("dict.history.title", lang, guild_name);
+        CreateEmbed::new().title(title).description(description)
+    };
+
+    let mut components = vec![];
+
+    // 選んだ変更を元に戻せるようにする
+    let select_options: Vec<_> = changes
+        .iter()
+        .map(|change| {
+            let label = truncate(&format!("#{} {}", change.id, change.word), 100);
+            let description = format!(
+                "{} -> {}",
+                entry_text(change.old.as_ref(), lang),
+                entry_text(change.new.as_ref(), lang)
+            );
+
+            CreateSelectMenuOption::new(label, change.id.to_string())
+                .description(truncate(&description, 100))
+        })
+        .collect();
+
+    if !select_options.is_empty() {
+        let select_menu = CreateSelectMenu::new(
+            lang_t!("customid.dict.select_revert"),
+            CreateSelectMenuKind::String {
+                options: select_options,
+            },
+        )
+        .placeholder(lang_t!("dict.select.revert", lang));
+
+        components.push(CreateActionRow::SelectMenu(select_menu));
+    }
+
+    Ok((embed, components))
+}
+
+/// 変更前後の読み方 (なければ「なし」)
+fn entry_text(entry: Option<&DictEntry>, lang: Lang) -> String {
+    match entry {
+        Some(entry) => truncate(&reading_text(entry), 40),
+        None => lang_t!("dict.history.none", lang).to_string(),
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use langrustang::{format_t, lang_t};
+use serenity::all::{Attachment, Context, GuildId, UserId};
+use sonorust_db::{DictEntry, GuildDataMut, Permission};
+
+use crate::{
+    _langrustang_autogen::Lang, components::button::dict_add::parse_entry,
+    crate_extensions::serenity::SerenityContextExt, errors::SonorustError,
+};
+
+use super::format::{DictFormat, ParseError};
+
+/// 読み込めるファイルの最大サイズ (バイト)
+const MAX_IMPORT_SIZE: u32 = 1024 * 1024;
+
+/// ファイルからサーバー辞書に読み込む
+/// (is_replace が true の場合は今の辞書を消してから読み込む)
+pub async fn dict_import(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+    attachment: &Attachment,
+    is_replace: bool,
+    lang: Lang,
+) -> Result<String, SonorustError> {
+    let guild_id = guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    // サーバー辞書を編集する権限がなければ返す
+    if !ctx
+        .has_permission(guild_id, user_id, Permission::ManageDict)
+        .await?
+    {
+        return Ok(lang_t!("msg.no_permission", lang).to_string());
+    }
+
+    if attachment.size > MAX_IMPORT_SIZE {
+        return Ok(format_t!(
+            "dict.import.too_large",
+            lang,
+            MAX_IMPORT_SIZE / 1024
+        ));
+    }
+
+    let bytes = attachment.download().await?;
+    let Ok(text) = String::from_utf8(bytes) else {
+        return Ok(lang_t!("dict.import.not_utf8", lang).to_string());
+    };
+
+    let Some(format) = DictFormat::detect(&attachment.filename, &text) else {
+        return Ok(lang_t!("dict.import.unknown_format", lang).to_string());
+    };
+
+    let parsed = match format.parse(&text) {
+        Ok(parsed) => parsed,
+        Err(ParseError::Json(err)) => {
+            return Ok(format_t!("dict.import.invalid_json", lang, err));
+        }
+        Err(ParseError::UnknownJson) => {
+            return Ok(lang_t!("dict.import.unknown_format", lang).to_string());
+        }
+        Err(ParseError::Csv(line)) => {
+            return Ok(format_t!("dict.import.invalid_csv", lang, line));
+        }
+    };
+
+    // 追加するときと同じ確認をして、使えない単語は飛ばす
+    let mut skipped = parsed.skipped;
+    let mut imported: HashMap<String, DictEntry> = HashMap::new();
+
+    for raw in parsed.value {
+        let word = raw.word.trim();
+        if word.is_empty() {
+            skipped += 1;
+            continue;
+        }
+
+        match parse_entry(word, &raw.reading, &raw.mode, &raw.priority, lang) {
+            Ok(entry) => {
+                imported.insert(word.to_string(), entry);
+            }
+            Err(_) => skipped += 1,
+        }
+    }
+
+    // 変更内容を数えてから更新する
+    let summary = {
+        let mut guilddata_mut = GuildDataMut::from(guild_id).await?;
+        let summary = ImportSummary::new(&guilddata_mut.dict, &imported, is_replace);
+
+        match is_replace {
+            true => guilddata_mut.dict = imported,
+            false => guilddata_mut.dict.extend(imported),
+        }
+
+        guilddata_mut.update_by(user_id).await?;
+        summary
+    };
+
+    Ok(format_t!(
+        "dict.import.done",
+        lang,
+        format.as_str(),
+        summary.added,
+        summary.changed,
+        summary.unchanged,
+        summary.removed,
+        skipped
+    ))
+}
+
+/// 読み込みで変わる単語の数
+#[derive(Debug, Default)]
+struct ImportSummary {
+    added: usize,
+    changed: usize,
+    unchanged: usize,
+    removed: usize,
+}
+
+impl ImportSummary {
+    fn new(
+        current: &HashMap<String, DictEntry>,
+        imported: &HashMap<String, DictEntry>,
+        is_replace: bool,
+    ) -> Self {
+        let mut summary = ImportSummary::default();
+
+        for (word, entry) in imported {
+            match current.get(word) {
+                None => summary.added += 1,
+                Some(current_entry) if current_entry == entry => summary.unchanged += 1,
+                Some(_) => summary.changed += 1,
+            }
+        }
+
+        if is_replace {
+            summary.removed = current
+                .keys()
+                .filter(|word| !imported.contains_key(*word))
+                .count();
+        }
+
+        summary
+    }
+}
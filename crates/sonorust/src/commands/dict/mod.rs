@@ -0,0 +1,296 @@
+pub mod export;
+pub mod format;
+pub mod history;
+pub mod import;
+
+pub use export::dict_export;
+pub use history::dict_history;
+pub use import::dict_import;
+
+use std::collections::HashMap;
+
+use langrustang::{format_t, lang_t};
+use serenity::all::{
+    ActionRow, ActionRowComponent, ButtonKind, ButtonStyle, CommandOptionType, Context,
+    CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed,
+    CreateEmbedFooter, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, GuildId,
+};
+use sonorust_db::{DictEntry, GuildData, MatchMode};
+
+use crate::{_langrustang_autogen::Lang, errors::SonorustError};
+
+use format::DictFormat;
+
+/// 1ページに表示する単語の数 (セレクトメニューに入れられる上限)
+const PAGE_SIZE: usize = 25;
+
+/// custom_id に入れる検索する文字列の最大の長さ
+const MAX_QUERY_LEN: usize = 80;
+
+pub async fn dict(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    lang: Lang,
+) -> Result<(CreateEmbed, Vec<CreateActionRow>), SonorustError> {
+    dict_page(ctx, guild_id, lang, "", 0).await
+}
+
+/// query を含む単語を単語順に並べて page ページ目 (0 から) を表示する
+pub async fn dict_page(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    lang: Lang,
+    query: &str,
+    page: usize,
+) -> Result<(CreateEmbed, Vec<CreateActionRow>), SonorustError> {
+    let guild_id = guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    let guilddata = GuildData::from(guild_id).await?;
+    let guild_name = guild_id
+        .name(&ctx.cache)
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let query: String = query.trim().chars().take(MAX_QUERY_LEN).collect();
+    let entries = search_entries(&guilddata.dict, &query);
+
+    // 単語が削除されてページが減った場合は最後のページにする
+    let page_count = entries.len().div_ceil(PAGE_SIZE).max(1);
+    let page = page.min(page_count - 1);
+    let page_entries = entries.chunks(PAGE_SIZE).nth(page).unwrap_or_default();
+
+    let embed = {
+        let description = match (guilddata.dict.is_empty(), page_entries.is_empty()) {
+            (true, _) => lang_t!("dict.unregistered", lang).to_string(),
+            (false, true) => format_t!("dict.search.not_found", lang, query),
+            (false, false) => page_entries
+                .iter()
+                .map(|(word, entry)| truncate(&entry_line(word, entry), 150))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+
+        let footer = match query.is_empty() {
+            true => format_t!(
+                "dict.embed.footer",
+                lang,
+                page + 1,
+                page_count,
+                entries.len()
+            ),
+            false => format_t!(
+                "dict.embed.footer_search",
+                lang,
+                query,
+                page + 1,
+                page_count,
+                entries.len()
+            ),
+        };
+
+        let title = format_t!("dict.embed.title", lang, guild_name);
+        CreateEmbed::new()
+            .title(title)
+            .description(description)
+            .footer(CreateEmbedFooter::new(footer))
+    };
+
+    let mut components = vec![];
+
+    // 表示している単語から選んで削除できるようにする (value に入らない長さの単語は除く)
+    let select_options: Vec<_> = page_entries
+        .iter()
+        .filter(|(word, _)| word.chars().count() <= 100)
+        .map(|(word, entry)| {
+            CreateSelectMenuOption::new(truncate(&entry_line(word, entry), 100), word.as_str())
+        })
+        .collect();
+
+    if !select_options.is_empty() {
+        let select_menu = CreateSelectMenu::new(
+            lang_t!("customid.dict.select_remove"),
+            CreateSelectMenuKind::String {
+                options: select_options,
+            },
+        )
+        .placeholder(lang_t!("dict.select.remove", lang));
+
+        components.push(CreateActionRow::SelectMenu(select_menu));
+    }
+
+    // 今のページと検索する文字列はページ番号のボタンに入れておく
+    let page_row = {
+        let page_back = CreateButton::new(lang_t!("customid.page.dict.back"))
+            .label("<-")
+            .style(ButtonStyle::Primary)
+            .disabled(page == 0);
+
+        let page_number =
+            CreateButton::new(format!("{}:{query}", lang_t!("customid.page.dict.number")))
+                .label((page + 1).to_string())
+                .style(ButtonStyle::Secondary)
+                .disabled(true);
+
+        let page_forward = CreateButton::new(lang_t!("customid.page.dict.forward"))
+            .label("->")
+            .style(ButtonStyle::Primary)
+            .disabled(page + 1 >= page_count);
+
+        CreateActionRow::Buttons(vec![page_back, page_number, page_forward])
+    };
+    components.push(page_row);
+
+    let button_row = {
+        let button_add = CreateButton::new(lang_t!("customid.dict.add"))
+            .label(lang_t!("dict.label.add"))
+            .style(ButtonStyle::Primary);
+        let button_remove = CreateButton::new(lang_t!("customid.dict.remove"))
+            .label(lang_t!("dict.label.remove"))
+            .style(ButtonStyle::Secondary);
+
+        CreateActionRow::Buttons(vec![button_add, button_remove])
+    };
+    components.push(button_row);
+
+    Ok((embed, components))
+}
+
+/// dict_page で作ったメッセージから今のページ (0 から) と検索する文字列を取得する
+pub(crate) fn page_state(rows: &[ActionRow]) -> Option<(usize, String)> {
+    let prefix = format!("{}:", lang_t!("customid.page.dict.number"));
+
+    rows.iter()
+        .flat_map(|row| &row.components)
+        .find_map(|component| {
+            let ActionRowComponent::Button(button) = component else {
+                return None;
+            };
+            let ButtonKind::NonLink { custom_id, .. } = &button.data else {
+                return None;
+            };
+
+            let query = custom_id.strip_prefix(&prefix)?;
+            let page: usize = button.label.as_ref()?.parse().ok()?;
+
+            Some((page.saturating_sub(1), query.to_string()))
+        })
+}
+
+/// 単語か読み方に query を含むものを単語順に並べる (大文字と小文字は区別しない)
+fn search_entries<'a>(
+    dict: &'a HashMap<String, DictEntry>,
+    query: &str,
+) -> Vec<(&'a String, &'a DictEntry)> {
+    let query = query.to_lowercase();
+
+    let mut entries: Vec<_> = dict
+        .iter()
+        .filter(|(word, entry)| {
+            query.is_empty()
+                || word.to_lowercase().contains(&query)
+                || entry.after_text.to_lowercase().contains(&query)
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+fn entry_line(word: &str, entry: &DictEntry) -> String {
+    format!("{word} -> {}", reading_text(entry))
+}
+
+/// 読み方 (一致方法か優先度が既定でなければ後ろにつける)
+fn reading_text(entry: &DictEntry) -> String {
+    match (entry.mode, entry.priority) {
+        (MatchMode::Literal, 0) => entry.after_text.clone(),
+        (mode, priority) => format!("{} ({}, {priority})", entry.after_text, mode.as_str()),
+    }
+}
+
+/// max_len 文字を超える場合は切り詰めて … をつける
+fn truncate(s: &str, max_len: usize) -> String {
+    match s.chars().count() > max_len {
+        true => s.chars().take(max_len - 1).chain(['…']).collect(),
+        false => s.to_string(),
+    }
+}
+
+/// 辞書の単語を単語順に一行ずつ並べた文字列
+pub(crate) fn dict_list(dict: &HashMap<String, DictEntry>) -> String {
+    search_entries(dict, "")
+        .into_iter()
+        .map(|(word, entry)| entry_line(word, entry))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn create_command(lang: Lang) -> CreateCommand {
+    let format_option = DictFormat::ALL.into_iter().fold(
+        CreateCommandOption::new(
+            CommandOptionType::String,
+            lang_t!("dict.option.format"),
+            lang_t!("dict.option.format.description", lang),
+        ),
+        |option, format| option.add_string_choice(format.as_str(), format.as_str()),
+    );
+
+    let mode_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        lang_t!("dict.option.mode"),
+        lang_t!("dict.option.mode.description", lang),
+    )
+    .add_string_choice(lang_t!("dict.option.mode.merge", lang), "merge")
+    .add_string_choice(lang_t!("dict.option.mode.replace", lang), "replace");
+
+    CreateCommand::new("dict")
+        .description(lang_t!("dict.command.description", lang))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            lang_t!("dict.subcommand.menu"),
+            lang_t!("dict.subcommand.menu.description", lang),
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                lang_t!("dict.subcommand.search"),
+                lang_t!("dict.subcommand.search.description", lang),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    lang_t!("dict.option.word"),
+                    lang_t!("dict.option.word.description", lang),
+                )
+                .required(true),
+            ),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            lang_t!("dict.subcommand.history"),
+            lang_t!("dict.subcommand.history.description", lang),
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                lang_t!("dict.subcommand.export"),
+                lang_t!("dict.subcommand.export.description", lang),
+            )
+            .add_sub_option(format_option),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                lang_t!("dict.subcommand.import"),
+                lang_t!("dict.subcommand.import.description", lang),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Attachment,
+                    lang_t!("dict.option.file"),
+                    lang_t!("dict.option.file.description", lang),
+                )
+                .required(true),
+            )
+            .add_sub_option(mode_option),
+        )
+}
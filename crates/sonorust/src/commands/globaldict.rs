@@ -0,0 +1,59 @@
+use langrustang::lang_t;
+use serenity::all::{
+    ButtonStyle, Context, CreateActionRow, CreateButton, CreateCommand, CreateEmbed, UserId,
+};
+use sonorust_db::GlobalDict;
+
+use crate::{
+    _langrustang_autogen::Lang, crate_extensions::serenity::SerenityHttpExt, errors::SonorustError,
+};
+
+use super::dict::dict_list;
+
+pub async fn globaldict(
+    ctx: &Context,
+    user_id: UserId,
+    lang: Lang,
+) -> Result<(CreateEmbed, Vec<CreateActionRow>), SonorustError> {
+    let global_dict = GlobalDict::get().await?;
+
+    let embed = {
+        let mut description = dict_list(&global_dict.dict);
+
+        if global_dict.dict.is_empty() {
+            description = lang_t!("globaldict.unregistered", lang).to_string();
+        }
+
+        if description.chars().count() >= 4000 {
+            description = lang_t!("dict.too_many", lang).to_string()
+        }
+
+        CreateEmbed::new()
+            .title(lang_t!("globaldict.embed.title", lang))
+            .description(description)
+    };
+
+    // bot の所有者以外には閲覧のみ
+    let app_owner_id = ctx.http.get_bot_owner_id().await;
+    if app_owner_id != user_id {
+        return Ok((embed, vec![]));
+    }
+
+    let component = {
+        let button_add = CreateButton::new(lang_t!("customid.globaldict.add"))
+            .label(lang_t!("dict.label.add"))
+            .style(ButtonStyle::Primary);
+        let button_remove = CreateButton::new(lang_t!("customid.globaldict.remove"))
+            .label(lang_t!("dict.label.remove"))
+            .style(ButtonStyle::Secondary);
+
+        let row = CreateActionRow::Buttons(vec![button_add, button_remove]);
+        vec![row]
+    };
+
+    Ok((embed, component))
+}
+
+pub fn create_command(lang: Lang) -> CreateCommand {
+    CreateCommand::new("globaldict").description(lang_t!("globaldict.command.description", lang))
+}
@@ -57,11 +57,31 @@ pub async fn help(ctx: &Context, lang: Lang, prefix: &str) -> CreateEmbed {
             &format_t!("autojoin.command.description", lang, prefix),
             IS_INLINE,
         ),
+        (
+            lang_t!("ignore.command.name"),
+            lang_t!("ignore.command.description", lang),
+            IS_INLINE,
+        ),
+        (
+            lang_t!("permission.command.name"),
+            lang_t!("permission.command.description", lang),
+            IS_INLINE,
+        ),
         (
             lang_t!("dict.command.name"),
             lang_t!("dict.command.description", lang),
             IS_INLINE,
         ),
+        (
+            lang_t!("mydict.command.name"),
+            lang_t!("mydict.command.description", lang),
+            IS_INLINE,
+        ),
+        (
+            lang_t!("globaldict.command.name"),
+            lang_t!("globaldict.command.description", lang),
+            IS_INLINE,
+        ),
         (
             lang_t!("reload.command.name"),
             lang_t!("reload.command.description", lang),
@@ -72,6 +92,21 @@ pub async fn help(ctx: &Context, lang: Lang, prefix: &str) -> CreateEmbed {
             lang_t!("length.command.description", lang),
             IS_INLINE,
         ),
+        (
+            lang_t!("read_me.command.name"),
+            lang_t!("read_me.command.description", lang),
+            IS_INLINE,
+        ),
+        (
+            lang_t!("read_name.command.name"),
+            lang_t!("read_name.command.description", lang),
+            IS_INLINE,
+        ),
+        (
+            lang_t!("preview.command.name"),
+            lang_t!("preview.command.description", lang),
+            IS_INLINE,
+        ),
         (
             lang_t!("wav.command.name"),
             lang_t!("wav.command.description", lang),
@@ -0,0 +1,100 @@
+use langrustang::lang_t;
+use serenity::all::{
+    CommandOptionType, Context, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter,
+    GuildId, UserId,
+};
+use sonorust_db::{GuildData, GuildDataMut, Permission};
+
+use crate::{
+    _langrustang_autogen::Lang, crate_extensions::serenity::SerenityContextExt as _,
+    errors::SonorustError,
+};
+
+pub async fn ignore(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+    lang: Lang,
+    target_user_id: Option<UserId>,
+) -> Result<(Option<CreateEmbed>, Option<&str>), SonorustError> {
+    match target_user_id {
+        Some(target) => ignore_setting(ctx, guild_id, user_id, lang, target).await,
+        None => Ok((Some(ignore_embed(guild_id, lang).await?), None)),
+    }
+}
+
+// target_user_id が None の場合
+async fn ignore_embed(guild_id: Option<GuildId>, lang: Lang) -> Result<CreateEmbed, SonorustError> {
+    let guild_id = guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
+    let guilddata = GuildData::from(guild_id).await?;
+
+    let mut description = guilddata
+        .ignored_users
+        .iter()
+        .map(|user_id| format!("<@{user_id}>"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if description.is_empty() {
+        description = lang_t!("ignore.unregistered", lang).to_string();
+    }
+
+    if description.chars().count() >= 4000 {
+        description = lang_t!("ignore.too_many", lang).to_string()
+    }
+
+    let embed = CreateEmbed::new()
+        .title(lang_t!("ignore.embed.title", lang))
+        .description(description)
+        .footer(CreateEmbedFooter::new(lang_t!("ignore.embed.footer", lang)));
+
+    Ok(embed)
+}
+
+async fn ignore_setting(
+    ctx: &Context,
+    option_guild_id: Option<GuildId>,
+    user_id: UserId,
+    lang: Lang,
+    target_user_id: UserId,
+) -> Result<(Option<CreateEmbed>, Option<&str>), SonorustError> {
+    let guild_id = option_guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
+    let is_permitted = ctx
+        .has_permission(guild_id, user_id, Permission::ManageServer)
+        .await?;
+
+    // サーバー設定を変更する権限がなければ
+    if !is_permitted {
+        return Ok((None, Some(lang_t!("msg.no_permission", lang))));
+    }
+
+    // 登録されていれば解除、されていなければ登録
+    let text = {
+        let mut guilddata_mut = GuildDataMut::from(guild_id).await?;
+
+        let text = match guilddata_mut.ignored_users.remove(&target_user_id) {
+            true => lang_t!("ignore.removed", lang),
+            false => {
+                guilddata_mut.ignored_users.insert(target_user_id);
+                lang_t!("ignore.inserted", lang)
+            }
+        };
+
+        guilddata_mut.update().await?;
+        text
+    };
+
+    let embed = ignore_embed(option_guild_id, lang).await?;
+
+    Ok((Some(embed), Some(text)))
+}
+
+pub fn create_command(lang: Lang) -> CreateCommand {
+    CreateCommand::new("ignore")
+        .description(lang_t!("ignore.command.description", lang))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::User,
+            lang_t!("ignore.option.user"),
+            lang_t!("ignore.option.user.description", lang),
+        ))
+}
@@ -2,6 +2,7 @@ use std::collections::{HashSet, VecDeque};
 
 use langrustang::lang_t;
 use serenity::all::{ChannelId, Context, CreateCommand, GuildId, UserId};
+use sonorust_db::GuildData;
 
 use crate::{Handler, _langrustang_autogen::Lang, crate_extensions::rwlock::RwLockExt};
 
@@ -60,9 +61,21 @@ pub async fn join(
     }
 
     // サーバーIDと読み上げるチャンネルIDのペアを登録
+    // オプションがオンならボイスチャンネルのチャットも読み上げる対象にする
+    let mut read_channels = HashSet::from([channel_id]);
+
+    match GuildData::from(guild_id).await {
+        Ok(guilddata) => {
+            if guilddata.options.is_read_vc_chat {
+                read_channels.insert(connect_ch);
+            }
+        }
+        Err(err) => log::error!("{}: {err}", lang_t!("log.fail_get_data")),
+    }
+
     handler
         .read_channels
-        .with_write(|lock| lock.insert(guild_id, HashSet::from([channel_id])));
+        .with_write(|lock| lock.insert(guild_id, read_channels));
 
     // 読み上げ queue を初期化
     handler
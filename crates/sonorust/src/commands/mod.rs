@@ -1,14 +1,21 @@
 pub mod autojoin;
 pub mod clear;
 pub mod dict;
+pub mod globaldict;
 pub mod help;
+pub mod ignore;
 pub mod join;
 pub mod leave;
 pub mod length;
 pub mod model;
+pub mod mydict;
 pub mod now;
+pub mod permission;
 pub mod ping;
+pub mod preview;
 pub mod read_add;
+pub mod read_me;
+pub mod read_name;
 pub mod read_remove;
 pub mod reload;
 pub mod server;
@@ -19,13 +26,20 @@ pub mod wav;
 pub use autojoin::autojoin;
 pub use clear::clear;
 pub use dict::dict;
+pub use globaldict::globaldict;
 pub use help::help;
+pub use ignore::ignore;
 pub use join::join;
 pub use leave::leave;
 pub use length::length;
 pub use model::model;
+pub use mydict::mydict;
 pub use now::now;
+pub use permission::permission;
+pub use preview::preview;
 pub use read_add::read_add;
+pub use read_me::read_me;
+pub use read_name::read_name;
 pub use read_remove::read_remove;
 pub use reload::reload;
 pub use server::server;
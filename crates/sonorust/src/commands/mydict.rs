@@ -0,0 +1,47 @@
+use langrustang::{format_t, lang_t};
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateCommand, CreateEmbed, User};
+use sonorust_db::UserData;
+
+use crate::{_langrustang_autogen::Lang, errors::SonorustError};
+
+use super::dict::dict_list;
+
+pub async fn mydict(
+    user: &User,
+    lang: Lang,
+) -> Result<(CreateEmbed, Vec<CreateActionRow>), SonorustError> {
+    let userdata = UserData::from(user.id).await?;
+
+    let embed = {
+        let mut description = dict_list(&userdata.dict);
+
+        if userdata.dict.is_empty() {
+            description = lang_t!("mydict.unregistered", lang).to_string();
+        }
+
+        if description.chars().count() >= 4000 {
+            description = lang_t!("dict.too_many", lang).to_string()
+        }
+
+        let title = format_t!("mydict.embed.title", lang, user.display_name());
+        CreateEmbed::new().title(title).description(description)
+    };
+
+    let component = {
+        let button_add = CreateButton::new(lang_t!("customid.mydict.add"))
+            .label(lang_t!("dict.label.add"))
+            .style(ButtonStyle::Primary);
+        let button_remove = CreateButton::new(lang_t!("customid.mydict.remove"))
+            .label(lang_t!("dict.label.remove"))
+            .style(ButtonStyle::Secondary);
+
+        let row = CreateActionRow::Buttons(vec![button_add, button_remove]);
+        vec![row]
+    };
+
+    Ok((embed, component))
+}
+
+pub fn create_command(lang: Lang) -> CreateCommand {
+    CreateCommand::new("mydict").description(lang_t!("mydict.command.description", lang))
+}
@@ -0,0 +1,143 @@
+use langrustang::lang_t;
+use serenity::all::{
+    CommandOptionType, Context, CreateCommand, CreateCommandOption, CreateEmbed, CreateEmbedFooter,
+    GuildId, RoleId, UserId,
+};
+use sonorust_db::{GuildData, GuildDataMut, Permission};
+
+use crate::{
+    _langrustang_autogen::Lang, crate_extensions::serenity::SerenityContextExt as _,
+    errors::SonorustError,
+};
+
+pub async fn permission(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+    lang: Lang,
+    permission_role: Option<(Permission, RoleId)>,
+) -> Result<(Option<CreateEmbed>, Option<&str>), SonorustError> {
+    match permission_role {
+        Some((permission, role_id)) => {
+            permission_setting(ctx, guild_id, user_id, lang, permission, role_id).await
+        }
+        None => Ok((Some(permission_embed(guild_id, lang).await?), None)),
+    }
+}
+
+// permission_role が None の場合
+async fn permission_embed(
+    guild_id: Option<GuildId>,
+    lang: Lang,
+) -> Result<CreateEmbed, SonorustError> {
+    let guild_id = guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
+    let guilddata = GuildData::from(guild_id).await?;
+
+    let fields = Permission::ALL.map(|permission| {
+        let roles = guilddata
+            .role_permissions
+            .get(&permission)
+            .filter(|roles| !roles.is_empty());
+
+        let value = match roles {
+            Some(roles) => roles
+                .iter()
+                .map(|role_id| format!("<@&{role_id}>"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            None => default_description(permission, lang).to_string(),
+        };
+
+        (permission_name(permission, lang), value, false)
+    });
+
+    let embed = CreateEmbed::new()
+        .title(lang_t!("permission.embed.title", lang))
+        .fields(fields)
+        .footer(CreateEmbedFooter::new(lang_t!(
+            "permission.embed.footer",
+            lang
+        )));
+
+    Ok(embed)
+}
+
+async fn permission_setting(
+    ctx: &Context,
+    option_guild_id: Option<GuildId>,
+    user_id: UserId,
+    lang: Lang,
+    permission: Permission,
+    role_id: RoleId,
+) -> Result<(Option<CreateEmbed>, Option<&str>), SonorustError> {
+    let guild_id = option_guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    // 権限の割り当ては管理者と bot の所有者のみ行える
+    if !ctx.is_admin_or_owner(guild_id, user_id).await? {
+        return Ok((None, Some(lang_t!("msg.only_admin", lang))));
+    }
+
+    // 登録されていれば解除、されていなければ登録
+    let text = {
+        let mut guilddata_mut = GuildDataMut::from(guild_id).await?;
+        let roles = guilddata_mut
+            .role_permissions
+            .entry(permission)
+            .or_default();
+
+        let text = match roles.remove(&role_id) {
+            true => lang_t!("permission.removed", lang),
+            false => {
+                roles.insert(role_id);
+                lang_t!("permission.inserted", lang)
+            }
+        };
+
+        guilddata_mut.update().await?;
+        text
+    };
+
+    let embed = permission_embed(option_guild_id, lang).await?;
+
+    Ok((Some(embed), Some(text)))
+}
+
+fn permission_name(permission: Permission, lang: Lang) -> &'static str {
+    match permission {
+        Permission::ManageDict => lang_t!("permission.name.manage_dict", lang),
+        Permission::ManageServer => lang_t!("permission.name.manage_server", lang),
+        Permission::Clear => lang_t!("permission.name.clear", lang),
+        Permission::BeRead => lang_t!("permission.name.be_read", lang),
+    }
+}
+
+// ロールが設定されていないときの動作
+fn default_description(permission: Permission, lang: Lang) -> &'static str {
+    match permission {
+        Permission::ManageDict => lang_t!("permission.default.manage_dict", lang),
+        Permission::ManageServer => lang_t!("permission.default.manage_server", lang),
+        Permission::Clear | Permission::BeRead => lang_t!("permission.default.everyone", lang),
+    }
+}
+
+pub fn create_command(lang: Lang) -> CreateCommand {
+    let permission_option = Permission::ALL.into_iter().fold(
+        CreateCommandOption::new(
+            CommandOptionType::String,
+            lang_t!("permission.option.permission"),
+            lang_t!("permission.option.permission.description", lang),
+        ),
+        |option, permission| {
+            option.add_string_choice(permission_name(permission, lang), permission.as_str())
+        },
+    );
+
+    CreateCommand::new("permission")
+        .description(lang_t!("permission.command.description", lang))
+        .add_option(permission_option)
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::Role,
+            lang_t!("permission.option.role"),
+            lang_t!("permission.option.role.description", lang),
+        ))
+}
@@ -0,0 +1,92 @@
+use langrustang::lang_t;
+use serenity::all::{
+    CommandOptionType, Context, CreateCommand, CreateCommandOption, CreateEmbed, GuildId, UserId,
+};
+use sonorust_db::{GlobalDict, GuildData, UserData};
+
+use crate::{
+    _langrustang_autogen::Lang,
+    commands::server::stage_title,
+    crate_extensions::rwlock::RwLockExt,
+    errors::SonorustError,
+    text_replace::{PipelineContext, TextReplace},
+    Handler,
+};
+
+// 1つの段階の結果として表示する最大の文字数
+// (全ての段階を表示しても embed の上限を超えないようにする)
+const PREVIEW_VALUE_LIMIT: usize = 300;
+
+/// 読み上げる文字が段階ごとにどう変換されるかを表示する
+pub async fn preview(
+    handler: &Handler,
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+    text: &str,
+    lang: Lang,
+) -> Result<CreateEmbed, SonorustError> {
+    let guild_id = guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    let guilddata = GuildData::from(guild_id).await?;
+    let userdata = UserData::from(user_id).await?;
+    let global_dict = GlobalDict::get().await?;
+    let emoji_repeat_limit = handler
+        .setting_json
+        .with_read(|lock| lock.emoji_repeat_limit);
+
+    let context = PipelineContext {
+        cache: &ctx.cache,
+        guild_id,
+        guilddata: &guilddata,
+        userdata: &userdata,
+        global_dict: &global_dict,
+        lang,
+        emoji_repeat_limit,
+    };
+
+    // 段階ごとに変換後の文字を記録する
+    let mut fields = vec![];
+    let mut last = text.to_string();
+
+    TextReplace::new(text).run_pipeline(&context, |stage, replaced| {
+        let value = match replaced {
+            "" => lang_t!("preview.empty", lang).to_string(),
+            _ if replaced == last => lang_t!("preview.unchanged", lang).to_string(),
+            _ => truncate(replaced),
+        };
+
+        let name = format!("{}. {}", fields.len() + 1, stage_title(stage, lang));
+        fields.push((name, value, false));
+
+        last = replaced.to_string();
+    });
+
+    Ok(CreateEmbed::new()
+        .title(lang_t!("preview.embed.title", lang))
+        .description(truncate(text))
+        .fields(fields))
+}
+
+fn truncate(text: &str) -> String {
+    match text.chars().count() > PREVIEW_VALUE_LIMIT {
+        true => {
+            let truncated: String = text.chars().take(PREVIEW_VALUE_LIMIT - 1).collect();
+            format!("{truncated}…")
+        }
+        false => text.to_string(),
+    }
+}
+
+pub fn create_command(lang: Lang) -> CreateCommand {
+    CreateCommand::new("preview")
+        .description(lang_t!("preview.command.description", lang))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                lang_t!("preview.option.text"),
+                lang_t!("preview.option.text.description", lang),
+            )
+            .required(true),
+        )
+}
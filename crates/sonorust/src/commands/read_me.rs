@@ -0,0 +1,27 @@
+use langrustang::lang_t;
+use serenity::all::{CreateCommand, UserId};
+use sonorust_db::UserDataMut;
+
+use crate::{_langrustang_autogen::Lang, errors::SonorustError};
+
+/// 自分のメッセージを読み上げるかどうかを切り替える
+pub async fn read_me(user_id: UserId, lang: Lang) -> Result<&'static str, SonorustError> {
+    let is_read_disabled = {
+        let mut userdata_mut = UserDataMut::from(user_id).await?;
+        userdata_mut.is_read_disabled = !userdata_mut.is_read_disabled;
+
+        let is_read_disabled = userdata_mut.is_read_disabled;
+        userdata_mut.update().await?;
+
+        is_read_disabled
+    };
+
+    match is_read_disabled {
+        true => Ok(lang_t!("read_me.disabled", lang)),
+        false => Ok(lang_t!("read_me.enabled", lang)),
+    }
+}
+
+pub fn create_command(lang: Lang) -> CreateCommand {
+    CreateCommand::new("read_me").description(lang_t!("read_me.command.description", lang))
+}
@@ -0,0 +1,50 @@
+use langrustang::{format_t, lang_t};
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption, UserId};
+use sonorust_db::UserDataMut;
+
+use crate::{_langrustang_autogen::Lang, errors::SonorustError};
+
+// 名前の読み方の最大文字数
+const READ_NAME_MAX_LEN: usize = 30;
+
+pub async fn read_name(
+    user_id: UserId,
+    read_name: Option<&str>,
+    lang: Lang,
+) -> Result<String, SonorustError> {
+    // 空白だけの場合はリセットとして扱う
+    let read_name = read_name.map(|s| s.trim()).filter(|s| !s.is_empty());
+
+    if let Some(name) = read_name {
+        if name.chars().count() > READ_NAME_MAX_LEN {
+            return Ok(format_t!("read_name.too_long", lang, READ_NAME_MAX_LEN));
+        }
+    }
+
+    // ユーザーデータを取得して更新
+    {
+        let mut userdata_mut = UserDataMut::from(user_id).await?;
+        userdata_mut.read_name = read_name.map(|s| s.to_string());
+
+        userdata_mut.update().await?;
+    }
+
+    match read_name {
+        Some(name) => Ok(format_t!("read_name.changed", lang, name)),
+        None => Ok(lang_t!("read_name.reset", lang).to_string()),
+    }
+}
+
+pub fn create_command(lang: Lang) -> CreateCommand {
+    CreateCommand::new("read_name")
+        .description(lang_t!("read_name.command.description", lang))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                lang_t!("read_name.option.name"),
+                lang_t!("read_name.option.name.description", lang),
+            )
+            .max_length(READ_NAME_MAX_LEN as u16)
+            .required(false),
+        )
+}
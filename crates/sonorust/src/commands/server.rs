@@ -1,12 +1,13 @@
 use langrustang::{format_t, lang_t};
 use serenity::all::{
-    Context, CreateActionRow, CreateCommand, CreateEmbed, CreateSelectMenu, CreateSelectMenuKind,
-    CreateSelectMenuOption, GuildId, UserId,
+    ActionRow, ActionRowComponent, ButtonStyle, Context, CreateActionRow, CreateButton,
+    CreateCommand, CreateEmbed, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    GuildId, UserId,
 };
-use sonorust_db::GuildData;
+use sonorust_db::{GuildData, Permission, Pipeline, PipelineStage};
 
 use crate::{
-    _langrustang_autogen::Lang, crate_extensions::serenity::SerenityHttpExt as _,
+    _langrustang_autogen::Lang, crate_extensions::serenity::SerenityContextExt as _,
     errors::SonorustError,
 };
 
@@ -15,23 +16,24 @@ pub async fn server(
     guild_id: Option<GuildId>,
     user_id: UserId,
     lang: Lang,
+) -> Result<(CreateEmbed, Vec<CreateActionRow>), SonorustError> {
+    server_with_stage(ctx, guild_id, user_id, None, lang).await
+}
+
+/// selected_stage を読み上げの変換の順番のメニューで選択した状態にして表示する
+pub async fn server_with_stage(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+    selected_stage: Option<PipelineStage>,
+    lang: Lang,
 ) -> Result<(CreateEmbed, Vec<CreateActionRow>), SonorustError> {
     let guild_id = guild_id.ok_or_else(|| SonorustError::GuildIdIsNone)?;
-    let member = guild_id.member(&ctx.http, user_id).await?;
     let guilddata = GuildData::from(guild_id).await?;
 
-    let is_bot_owner = {
-        let app_owner_id = ctx.http.get_bot_owner_id().await;
-        app_owner_id == user_id
-    };
-
-    let is_admin = {
-        #[allow(deprecated)]
-        match member.permissions(&ctx.cache) {
-            Ok(permissons) => permissons.administrator(),
-            Err(_) => false,
-        }
-    };
+    let is_permitted = ctx
+        .has_permission(guild_id, user_id, Permission::ManageServer)
+        .await?;
 
     let guild_name = guild_id
         .name(&ctx.cache)
@@ -71,17 +73,176 @@ pub async fn server(
                 format!("{}", bool_to_onoff(guilddata.options.is_if_long_fastread)),
                 false,
             ),
+            (
+                lang_t!("guild.desc.is_read_vc_chat", lang),
+                bool_to_onoff(guilddata.options.is_read_vc_chat).to_string(),
+                false,
+            ),
+            (
+                lang_t!("guild.desc.is_reread_edited", lang),
+                bool_to_onoff(guilddata.options.is_reread_edited).to_string(),
+                false,
+            ),
+            (
+                lang_t!("guild.desc.is_read_name", lang),
+                bool_to_onoff(guilddata.options.is_read_name).to_string(),
+                false,
+            ),
+            (
+                lang_t!("guild.desc.is_read_reply", lang),
+                bool_to_onoff(guilddata.options.is_read_reply).to_string(),
+                false,
+            ),
+            (
+                lang_t!("guild.desc.is_romaji_to_kana", lang),
+                bool_to_onoff(guilddata.options.is_romaji_to_kana).to_string(),
+                false,
+            ),
+            (
+                lang_t!("guild.desc.is_skip_spoiler", lang),
+                bool_to_onoff(guilddata.options.is_skip_spoiler).to_string(),
+                false,
+            ),
+            (
+                lang_t!("guild.desc.is_read_url_domain", lang),
+                bool_to_onoff(guilddata.options.is_read_url_domain).to_string(),
+                false,
+            ),
         ];
 
-        CreateEmbed::new().fields(fields).title(title)
+        // 読み上げの変換の順番
+        let pipeline = guilddata
+            .pipeline
+            .stages()
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let title = stage_title(s.stage, lang);
+                format!("{}. {title}: {}", i + 1, bool_to_onoff(s.is_enabled))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        CreateEmbed::new()
+            .fields(fields)
+            .field(lang_t!("server.pipeline.title", lang), pipeline, false)
+            .title(title)
     };
 
-    match is_admin || is_bot_owner {
-        true => Ok((embed, vec![create_select_menu(lang)])),
+    match is_permitted {
+        true => {
+            let mut components = vec![create_select_menu(lang)];
+            components.extend(create_pipeline_components(
+                &guilddata.pipeline,
+                selected_stage,
+                lang,
+            ));
+
+            Ok((embed, components))
+        }
         false => Ok((embed, vec![])),
     }
 }
 
+/// 段階の表示名
+pub(crate) fn stage_title(stage: PipelineStage, lang: Lang) -> &'static str {
+    match stage {
+        PipelineStage::Codeblock => lang_t!("server.pipeline.stage.codeblock", lang),
+        PipelineStage::Markdown => lang_t!("server.pipeline.stage.markdown", lang),
+        PipelineStage::FixError => lang_t!("server.pipeline.stage.fix_error", lang),
+        PipelineStage::Url => lang_t!("server.pipeline.stage.url", lang),
+        PipelineStage::DiscordObject => lang_t!("server.pipeline.stage.discord_object", lang),
+        PipelineStage::GlobalDict => lang_t!("server.pipeline.stage.global_dict", lang),
+        PipelineStage::GuildDict => lang_t!("server.pipeline.stage.guild_dict", lang),
+        PipelineStage::UserDict => lang_t!("server.pipeline.stage.user_dict", lang),
+        PipelineStage::Kaomoji => lang_t!("server.pipeline.stage.kaomoji", lang),
+        PipelineStage::Laugh => lang_t!("server.pipeline.stage.laugh", lang),
+        PipelineStage::CollapseRepeat => lang_t!("server.pipeline.stage.collapse_repeat", lang),
+        PipelineStage::LimitPunctuation => {
+            lang_t!("server.pipeline.stage.limit_punctuation", lang)
+        }
+        PipelineStage::Emoji => lang_t!("server.pipeline.stage.emoji", lang),
+        PipelineStage::Number => lang_t!("server.pipeline.stage.number", lang),
+        PipelineStage::EngToKana => lang_t!("server.pipeline.stage.eng_to_kana", lang),
+        PipelineStage::RemoveSymbol => lang_t!("server.pipeline.stage.remove_symbol", lang),
+    }
+}
+
+/// 段階の選択と、選択した段階の ON/OFF 切り替え、前後への移動、リセットのコンポーネント
+fn create_pipeline_components(
+    pipeline: &Pipeline,
+    selected_stage: Option<PipelineStage>,
+    lang: Lang,
+) -> Vec<CreateActionRow> {
+    let options = pipeline
+        .stages()
+        .iter()
+        .map(|s| {
+            CreateSelectMenuOption::new(stage_title(s.stage, lang), s.stage.as_str())
+                .default_selection(selected_stage == Some(s.stage))
+        })
+        .collect();
+
+    let select_menu = CreateSelectMenu::new(
+        lang_t!("customid.server.pipeline_select"),
+        CreateSelectMenuKind::String { options },
+    )
+    .placeholder(lang_t!("server.pipeline.select", lang));
+
+    // 段階を選択するまでは押せないようにする
+    let create_button = |custom_id: &str, label: &str| {
+        CreateButton::new(custom_id)
+            .label(label)
+            .style(ButtonStyle::Primary)
+            .disabled(selected_stage.is_none())
+    };
+
+    let reset_button = CreateButton::new(lang_t!("customid.server.pipeline_reset"))
+        .label(lang_t!("server.pipeline.reset", lang))
+        .style(ButtonStyle::Secondary);
+
+    vec![
+        CreateActionRow::SelectMenu(select_menu),
+        CreateActionRow::Buttons(vec![
+            create_button(
+                lang_t!("customid.server.pipeline_toggle"),
+                lang_t!("server.pipeline.toggle", lang),
+            ),
+            create_button(
+                lang_t!("customid.server.pipeline_up"),
+                lang_t!("server.pipeline.up", lang),
+            ),
+            create_button(
+                lang_t!("customid.server.pipeline_down"),
+                lang_t!("server.pipeline.down", lang),
+            ),
+            reset_button,
+        ]),
+    ]
+}
+
+/// server で作ったメッセージから選択されている段階を取得する
+pub(crate) fn selected_stage(rows: &[ActionRow]) -> Option<PipelineStage> {
+    rows.iter()
+        .flat_map(|row| &row.components)
+        .find_map(|component| {
+            let ActionRowComponent::SelectMenu(select_menu) = component else {
+                return None;
+            };
+
+            if select_menu.custom_id.as_deref() != Some(lang_t!("customid.server.pipeline_select"))
+            {
+                return None;
+            }
+
+            select_menu
+                .options
+                .iter()
+                .find(|option| option.default)
+                .and_then(|option| PipelineStage::from_name(&option.value))
+        })
+}
+
 fn create_select_menu(lang: Lang) -> CreateActionRow {
     let dic_only_admin = CreateSelectMenuOption::new(
         lang_t!("guild.desc.is_dic_onlyadmin", lang),
@@ -103,6 +264,34 @@ fn create_select_menu(lang: Lang) -> CreateActionRow {
         lang_t!("guild.desc.is_if_long_fastread", lang),
         lang_t!("guild.is_if_long_fastread"),
     );
+    let is_read_vc_chat = CreateSelectMenuOption::new(
+        lang_t!("guild.desc.is_read_vc_chat", lang),
+        lang_t!("guild.is_read_vc_chat"),
+    );
+    let is_reread_edited = CreateSelectMenuOption::new(
+        lang_t!("guild.desc.is_reread_edited", lang),
+        lang_t!("guild.is_reread_edited"),
+    );
+    let is_read_name = CreateSelectMenuOption::new(
+        lang_t!("guild.desc.is_read_name", lang),
+        lang_t!("guild.is_read_name"),
+    );
+    let is_read_reply = CreateSelectMenuOption::new(
+        lang_t!("guild.desc.is_read_reply", lang),
+        lang_t!("guild.is_read_reply"),
+    );
+    let is_romaji_to_kana = CreateSelectMenuOption::new(
+        lang_t!("guild.desc.is_romaji_to_kana", lang),
+        lang_t!("guild.is_romaji_to_kana"),
+    );
+    let is_skip_spoiler = CreateSelectMenuOption::new(
+        lang_t!("guild.desc.is_skip_spoiler", lang),
+        lang_t!("guild.is_skip_spoiler"),
+    );
+    let is_read_url_domain = CreateSelectMenuOption::new(
+        lang_t!("guild.desc.is_read_url_domain", lang),
+        lang_t!("guild.is_read_url_domain"),
+    );
 
     let select_menu = CreateSelectMenu::new(
         lang_t!("customid.change_server_settings"),
@@ -113,6 +302,13 @@ fn create_select_menu(lang: Lang) -> CreateActionRow {
                 is_entrance_exit_play,
                 is_notice_attachment,
                 is_if_long_fastread,
+                is_read_vc_chat,
+                is_reread_edited,
+                is_read_name,
+                is_read_reply,
+                is_romaji_to_kana,
+                is_skip_spoiler,
+                is_read_url_domain,
             ],
         },
     )
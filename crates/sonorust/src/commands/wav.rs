@@ -11,7 +11,7 @@ use crate::{
     errors::SonorustError,
     Handler,
     _langrustang_autogen::Lang,
-    registers::TextReplace,
+    text_replace::TextReplace,
 };
 
 pub async fn wav(
@@ -1,10 +1,14 @@
 use langrustang::{format_t, lang_t};
-use serenity::all::{ComponentInteraction, Context, CreateQuickModal, ModalInteraction};
-use sonorust_db::{GuildData, GuildDataMut};
+use serenity::all::{
+    ComponentInteraction, Context, CreateInputText, CreateQuickModal, InputTextStyle,
+    ModalInteraction,
+};
+use sonorust_db::{DictEntry, GuildDataMut, MatchMode, Permission};
 
 use crate::{
-    crate_extensions::{serenity::SerenityHttpExt, sonorust_setting::SettingJsonExt},
+    crate_extensions::{serenity::SerenityContextExt, sonorust_setting::SettingJsonExt},
     errors::SonorustError,
+    text_replace::dict::build_regex,
     Handler,
     _langrustang_autogen::Lang,
 };
@@ -19,32 +23,17 @@ pub async fn dict_add(
     let guild_id = interaction
         .guild_id
         .ok_or_else(|| SonorustError::GuildIdIsNone)?;
-    let guild_data = GuildData::from(guild_id).await?;
-
-    let inter_member = guild_id.member(&ctx.http, interaction.user.id).await?;
-
-    let is_bot_owner = {
-        let app_owner_id = ctx.http.get_bot_owner_id().await;
-        app_owner_id == interaction.user.id
-    };
-
-    let is_admin = {
-        #[allow(deprecated)]
-        match inter_member.permissions(&ctx.cache) {
-            Ok(permissons) => permissons.administrator(),
-            Err(_) => false,
-        }
-    };
 
-    // サーバー辞書の編集が管理者に限定されていた場合
-    // もしサーバーの管理者でないなら返す (BOT の所有者の場合許可)
-    let is_dic_adminonly = guild_data.options.is_dic_onlyadmin;
+    // サーバー辞書を編集する権限がなければ返す
+    let is_permitted = ctx
+        .has_permission(guild_id, interaction.user.id, Permission::ManageDict)
+        .await?;
 
-    if (is_dic_adminonly && !is_admin) && !is_bot_owner {
+    if !is_permitted {
         eq_uilibrium::create_response_msg!(
             interaction,
             &ctx.http,
-            content = lang_t!("msg.only_admin", lang),
+            content = lang_t!("msg.no_permission", lang),
             ephemeral = true
         )
         .await?;
@@ -64,11 +53,67 @@ pub async fn dict_add(
     Ok(())
 }
 
-fn create_quickmodal(lang: Lang) -> CreateQuickModal {
+pub(crate) fn create_quickmodal(lang: Lang) -> CreateQuickModal {
     CreateQuickModal::new(lang_t!("dict.modal.add.title", lang))
         .timeout(std::time::Duration::from_secs(600))
         .short_field(lang_t!("dict.modal.add.word", lang))
         .short_field(lang_t!("dict.modal.add.readings", lang))
+        .field(
+            CreateInputText::new(
+                InputTextStyle::Short,
+                lang_t!("dict.modal.add.mode", lang),
+                "",
+            )
+            .placeholder("literal / word / regex")
+            .required(false),
+        )
+        .field(
+            CreateInputText::new(
+                InputTextStyle::Short,
+                lang_t!("dict.modal.add.priority", lang),
+                "",
+            )
+            .placeholder("0")
+            .required(false),
+        )
+}
+
+/// 入力内容から辞書の読み方を作る (不正な入力の場合はエラーメッセージを返す)
+pub(crate) fn parse_entry(
+    key: &str,
+    value: &str,
+    mode: &str,
+    priority: &str,
+    lang: Lang,
+) -> Result<DictEntry, String> {
+    let mode = match mode.trim() {
+        "" => MatchMode::default(),
+        mode => MatchMode::from_name(&mode.to_lowercase())
+            .ok_or_else(|| format_t!("dict.modal.add.invalid_mode", lang, mode))?,
+    };
+
+    let priority = match priority.trim() {
+        "" => 0,
+        priority => priority
+            .parse()
+            .map_err(|_| format_t!("dict.modal.add.invalid_priority", lang, priority))?,
+    };
+
+    if mode == MatchMode::Regex {
+        let regex =
+            build_regex(key).map_err(|err| format_t!("dict.modal.add.invalid_regex", lang, err))?;
+
+        // 空文字に一致すると全ての位置に読み方が挿入されてしまう
+        if regex.is_match("") {
+            return Err(lang_t!("dict.modal.add.empty_match", lang).to_string());
+        }
+    }
+
+    Ok(DictEntry {
+        after_text: value.to_string(),
+        mode,
+        priority,
+    })
 }
 
 async fn on_submit(
@@ -79,15 +124,33 @@ async fn on_submit(
 ) -> Result<(), SonorustError> {
     // 入力内容の取得
     let (key, value) = (&inputs[0], &inputs[1]);
+    let mode = inputs.get(2).map(String::as_str).unwrap_or("");
+    let priority = inputs.get(3).map(String::as_str).unwrap_or("");
+
     let guild_id = interaction
         .guild_id
         .ok_or_else(|| SonorustError::GuildIdIsNone)?;
 
+    let entry = match parse_entry(key, value, mode, priority, lang) {
+        Ok(entry) => entry,
+        Err(err_msg) => {
+            eq_uilibrium::create_response_msg!(
+                interaction,
+                &ctx.http,
+                content = err_msg,
+                ephemeral = true
+            )
+            .await?;
+
+            return Ok(());
+        }
+    };
+
     {
         let mut guild_data_mut = GuildDataMut::from(guild_id).await?;
-        guild_data_mut.dict.insert(key.clone(), value.clone());
+        guild_data_mut.dict.insert(key.clone(), entry);
 
-        guild_data_mut.update().await?;
+        guild_data_mut.update_by(interaction.user.id).await?;
     }
 
     // 返答するメッセージを作成
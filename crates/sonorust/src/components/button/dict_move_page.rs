@@ -0,0 +1,41 @@
+use langrustang::lang_t;
+use serenity::all::{
+    ComponentInteraction, Context, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+
+use crate::{
+    commands::dict, crate_extensions::sonorust_setting::SettingJsonExt as _, errors::SonorustError,
+    Handler,
+};
+
+pub async fn dict_move_page(
+    handler: &Handler,
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    custom_id: &str,
+) -> Result<(), SonorustError> {
+    let lang = handler.setting_json.get_bot_lang();
+
+    // 現在のページと検索している文字列を取得
+    let (current_page, query) =
+        dict::page_state(&interaction.message.components).unwrap_or_default();
+
+    let page = match custom_id {
+        lang_t!("customid.page.dict.forward") => current_page + 1,
+        lang_t!("customid.page.dict.back") => current_page.saturating_sub(1),
+
+        _ => unreachable!(),
+    };
+
+    let (embed, components) =
+        dict::dict_page(ctx, interaction.guild_id, lang, &query, page).await?;
+
+    let builder = CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(components),
+    );
+    interaction.create_response(&ctx.http, builder).await?;
+
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use langrustang::{format_t, lang_t};
 use serenity::all::{ComponentInteraction, Context, CreateQuickModal, ModalInteraction};
-use sonorust_db::{GuildData, GuildDataMut};
+use sonorust_db::{GuildDataMut, Permission};
 
 use crate::{
-    crate_extensions::{serenity::SerenityHttpExt as _, sonorust_setting::SettingJsonExt as _},
+    crate_extensions::{serenity::SerenityContextExt as _, sonorust_setting::SettingJsonExt as _},
     errors::SonorustError,
     Handler,
     _langrustang_autogen::Lang,
@@ -19,32 +19,17 @@ pub async fn dict_remove(
     let guild_id = interaction
         .guild_id
         .ok_or_else(|| SonorustError::GuildIdIsNone)?;
-    let guild_data = GuildData::from(guild_id).await?;
 
-    let inter_member = guild_id.member(&ctx.http, interaction.user.id).await?;
-
-    let is_bot_owner = {
-        let app_owner_id = ctx.http.get_bot_owner_id().await;
-        app_owner_id == interaction.user.id
-    };
-
-    let is_admin = {
-        #[allow(deprecated)]
-        match inter_member.permissions(&ctx.cache) {
-            Ok(permissons) => permissons.administrator(),
-            Err(_) => false,
-        }
-    };
-
-    // サーバー辞書の編集が管理者に限定されていた場合
-    // もしサーバーの管理者でないなら返す (BOT の所有者の場合許可)
-    let is_dic_adminonly = guild_data.options.is_dic_onlyadmin;
+    // サーバー辞書を編集する権限がなければ返す
+    let is_permitted = ctx
+        .has_permission(guild_id, interaction.user.id, Permission::ManageDict)
+        .await?;
 
-    if (is_dic_adminonly && !is_admin) && !is_bot_owner {
+    if !is_permitted {
         eq_uilibrium::create_response_msg!(
             interaction,
             &ctx.http,
-            content = lang_t!("msg.only_admin", lang),
+            content = lang_t!("msg.no_permission", lang),
             ephemeral = true
         )
         .await?;
@@ -64,7 +49,7 @@ pub async fn dict_remove(
     Ok(())
 }
 
-fn create_quickmodal(lang: Lang) -> CreateQuickModal {
+pub(crate) fn create_quickmodal(lang: Lang) -> CreateQuickModal {
     CreateQuickModal::new(lang_t!("dict.modal.remove.title", lang))
         .timeout(std::time::Duration::from_secs(600))
         .short_field(lang_t!("dict.modal.remove.field", lang))
@@ -88,7 +73,7 @@ async fn on_submit(
         let removed = guilddata_mut.dict.remove(key);
 
         if removed.is_some() {
-            guilddata_mut.update().await?;
+            guilddata_mut.update_by(interaction.user.id).await?;
         }
         removed
     };
@@ -0,0 +1,76 @@
+use langrustang::{format_t, lang_t};
+use serenity::all::{ComponentInteraction, Context, ModalInteraction};
+use sonorust_db::GlobalDictMut;
+
+use crate::{
+    _langrustang_autogen::Lang,
+    crate_extensions::{serenity::SerenityHttpExt, sonorust_setting::SettingJsonExt as _},
+    errors::SonorustError,
+    Handler,
+};
+
+use super::dict_remove::create_quickmodal;
+
+pub async fn globaldict_remove(
+    handler: &Handler,
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), SonorustError> {
+    let lang = handler.setting_json.get_bot_lang();
+
+    // bot の所有者でなければ返す
+    let app_owner_id = ctx.http.get_bot_owner_id().await;
+    if app_owner_id != interaction.user.id {
+        eq_uilibrium::create_response_msg!(
+            interaction,
+            &ctx.http,
+            content = lang_t!("msg.only_owner", lang),
+            ephemeral = true
+        )
+        .await?;
+
+        return Ok(());
+    }
+
+    // modal の送信と処理
+    let modal = create_quickmodal(lang);
+    let Ok(Some(response)) = interaction.quick_modal(ctx, modal).await else {
+        return Ok(());
+    };
+
+    let inputs = response.inputs;
+    on_submit(ctx, &response.interaction, inputs, lang).await?;
+
+    Ok(())
+}
+
+async fn on_submit(
+    ctx: &Context,
+    interaction: &ModalInteraction,
+    inputs: Vec<String>,
+    lang: Lang,
+) -> Result<(), SonorustError> {
+    // 入力内容の取得
+    let key = &inputs[0];
+
+    let removed = {
+        let mut global_dict_mut = GlobalDictMut::get().await?;
+        let removed = global_dict_mut.dict.remove(key);
+
+        if removed.is_some() {
+            global_dict_mut.update().await?;
+        }
+        removed
+    };
+
+    // 返答するメッセージを作成
+    let content = match removed {
+        Some(_) => format_t!("dict.modal.remove.deleted", lang, key),
+        None => format_t!("dict.modal.remove.not_found", lang, key),
+    };
+
+    eq_uilibrium::create_response_msg!(interaction, &ctx.http, content = content, ephemeral = true)
+        .await?;
+
+    Ok(())
+}
@@ -1,7 +1,21 @@
 pub mod dict_add;
+pub mod dict_move_page;
 pub mod dict_remove;
+pub mod globaldict_add;
+pub mod globaldict_remove;
 pub mod move_page;
+pub mod mydict_add;
+pub mod mydict_remove;
+pub mod server_pipeline;
+pub mod server_pipeline_reset;
 
 pub use dict_add::dict_add;
+pub use dict_move_page::dict_move_page;
 pub use dict_remove::dict_remove;
+pub use globaldict_add::globaldict_add;
+pub use globaldict_remove::globaldict_remove;
 pub use move_page::move_page;
+pub use mydict_add::mydict_add;
+pub use mydict_remove::mydict_remove;
+pub use server_pipeline::server_pipeline;
+pub use server_pipeline_reset::server_pipeline_reset;
@@ -0,0 +1,74 @@
+use langrustang::format_t;
+use serenity::all::{ComponentInteraction, Context, ModalInteraction};
+use sonorust_db::UserDataMut;
+
+use crate::{
+    _langrustang_autogen::Lang, crate_extensions::sonorust_setting::SettingJsonExt,
+    errors::SonorustError, Handler,
+};
+
+use super::dict_add::{create_quickmodal, parse_entry};
+
+pub async fn mydict_add(
+    handler: &Handler,
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), SonorustError> {
+    let lang = handler.setting_json.get_bot_lang();
+
+    // modal の送信と処理 (個人辞書なので権限の確認はしない)
+    let modal = create_quickmodal(lang);
+    let Ok(Some(response)) = interaction.quick_modal(ctx, modal).await else {
+        return Ok(());
+    };
+
+    let inputs = response.inputs;
+    on_submit(ctx, &response.interaction, inputs, lang).await?;
+
+    Ok(())
+}
+
+async fn on_submit(
+    ctx: &Context,
+    interaction: &ModalInteraction,
+    inputs: Vec<String>,
+    lang: Lang,
+) -> Result<(), SonorustError> {
+    // 入力内容の取得
+    let (key, value) = (&inputs[0], &inputs[1]);
+    let mode = inputs.get(2).map(String::as_str).unwrap_or("");
+    let priority = inputs.get(3).map(String::as_str).unwrap_or("");
+
+    let entry = match parse_entry(key, value, mode, priority, lang) {
+        Ok(entry) => entry,
+        Err(err_msg) => {
+            eq_uilibrium::create_response_msg!(
+                interaction,
+                &ctx.http,
+                content = err_msg,
+                ephemeral = true
+            )
+            .await?;
+
+            return Ok(());
+        }
+    };
+
+    {
+        let mut userdata_mut = UserDataMut::from(interaction.user.id).await?;
+        userdata_mut.dict.insert(key.clone(), entry);
+
+        userdata_mut.update().await?;
+    }
+
+    // 返答するメッセージを作成
+    eq_uilibrium::create_response_msg!(
+        interaction,
+        &ctx.http,
+        content = format_t!("dict.modal.add.set", lang, key, value),
+        ephemeral = true
+    )
+    .await?;
+
+    Ok(())
+}
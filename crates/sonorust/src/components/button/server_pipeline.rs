@@ -0,0 +1,92 @@
+use langrustang::lang_t;
+use serenity::all::{
+    ComponentInteraction, Context, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use sonorust_db::{GuildDataMut, Permission};
+
+use crate::{
+    commands,
+    crate_extensions::{serenity::SerenityContextExt as _, sonorust_setting::SettingJsonExt as _},
+    errors::SonorustError,
+    Handler,
+};
+
+pub async fn server_pipeline(
+    handler: &Handler,
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    custom_id: &str,
+) -> Result<(), SonorustError> {
+    let lang = handler.setting_json.get_bot_lang();
+
+    let guild_id = interaction
+        .guild_id
+        .ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    let send_ephemeral_msg = |content: &str| {
+        eq_uilibrium::create_response_msg!(
+            interaction,
+            &ctx.http,
+            content = content,
+            ephemeral = true
+        )
+    };
+
+    // サーバー設定を変更する権限がなければ返す
+    let is_permitted = ctx
+        .has_permission(guild_id, interaction.user.id, Permission::ManageServer)
+        .await?;
+
+    if !is_permitted {
+        send_ephemeral_msg(lang_t!("msg.no_permission", lang)).await?;
+        return Ok(());
+    }
+
+    // メニューで選択されている段階を取得
+    let Some(stage) = commands::server::selected_stage(&interaction.message.components) else {
+        log::error!(lang_t!("log.fail_get_data"));
+        send_ephemeral_msg(lang_t!("msg.failed.get", lang)).await?;
+        return Ok(());
+    };
+
+    // サーバーデータの更新
+    {
+        let mut guilddata_mut = GuildDataMut::from(guild_id).await?;
+        let pipeline = &mut guilddata_mut.pipeline;
+
+        match custom_id {
+            lang_t!("customid.server.pipeline_toggle") => {
+                pipeline.set_enabled(stage, !pipeline.is_enabled(stage))
+            }
+            lang_t!("customid.server.pipeline_up") => pipeline.move_stage(stage, -1),
+            lang_t!("customid.server.pipeline_down") => pipeline.move_stage(stage, 1),
+
+            _ => {
+                log::error!("{}", lang_t!("log.not_implemented_customid"));
+                send_ephemeral_msg(lang_t!("msg.failed.get", lang)).await?;
+                return Ok(());
+            }
+        }
+
+        guilddata_mut.update().await?;
+    }
+
+    // 段階を選択したまま変更後の設定で表示し直す
+    let (embed, components) = commands::server::server_with_stage(
+        ctx,
+        Some(guild_id),
+        interaction.user.id,
+        Some(stage),
+        lang,
+    )
+    .await?;
+
+    let builder = CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(components),
+    );
+    interaction.create_response(&ctx.http, builder).await?;
+
+    Ok(())
+}
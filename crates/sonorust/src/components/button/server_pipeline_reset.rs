@@ -0,0 +1,60 @@
+use langrustang::lang_t;
+use serenity::all::{
+    ComponentInteraction, Context, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use sonorust_db::{GuildDataMut, Permission, Pipeline};
+
+use crate::{
+    commands,
+    crate_extensions::{serenity::SerenityContextExt as _, sonorust_setting::SettingJsonExt as _},
+    errors::SonorustError,
+    Handler,
+};
+
+pub async fn server_pipeline_reset(
+    handler: &Handler,
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), SonorustError> {
+    let lang = handler.setting_json.get_bot_lang();
+
+    let guild_id = interaction
+        .guild_id
+        .ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    // サーバー設定を変更する権限がなければ返す
+    let is_permitted = ctx
+        .has_permission(guild_id, interaction.user.id, Permission::ManageServer)
+        .await?;
+
+    if !is_permitted {
+        eq_uilibrium::create_response_msg!(
+            interaction,
+            &ctx.http,
+            content = lang_t!("msg.no_permission", lang),
+            ephemeral = true
+        )
+        .await?;
+
+        return Ok(());
+    }
+
+    // 読み上げの変換の順番を初期設定に戻す
+    {
+        let mut guilddata_mut = GuildDataMut::from(guild_id).await?;
+        guilddata_mut.pipeline = Pipeline::default();
+        guilddata_mut.update().await?;
+    }
+
+    let (embed, components) =
+        commands::server(ctx, Some(guild_id), interaction.user.id, lang).await?;
+
+    let builder = CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(components),
+    );
+    interaction.create_response(&ctx.http, builder).await?;
+
+    Ok(())
+}
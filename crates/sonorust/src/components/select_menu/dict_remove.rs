@@ -0,0 +1,91 @@
+use langrustang::{format_t, lang_t};
+use serenity::all::{
+    ComponentInteraction, ComponentInteractionDataKind, Context, CreateInteractionResponse,
+    CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
+};
+use sonorust_db::{GuildDataMut, Permission};
+
+use crate::{
+    commands::dict,
+    crate_extensions::{serenity::SerenityContextExt as _, sonorust_setting::SettingJsonExt as _},
+    errors::SonorustError,
+    Handler,
+};
+
+pub async fn dict_remove(
+    handler: &Handler,
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), SonorustError> {
+    let lang = handler.setting_json.get_bot_lang();
+
+    let guild_id = interaction
+        .guild_id
+        .ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    let send_ephemeral_msg = |content: String| {
+        eq_uilibrium::create_response_msg!(
+            interaction,
+            &ctx.http,
+            content = content,
+            ephemeral = true
+        )
+    };
+
+    // サーバー辞書を編集する権限がなければ返す
+    let is_permitted = ctx
+        .has_permission(guild_id, interaction.user.id, Permission::ManageDict)
+        .await?;
+
+    if !is_permitted {
+        send_ephemeral_msg(lang_t!("msg.no_permission", lang).to_string()).await?;
+        return Ok(());
+    }
+
+    // 選択した単語を取得
+    let key = match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values[0].as_str(),
+
+        _ => {
+            log::error!(lang_t!("log.fail_get_data"));
+            send_ephemeral_msg(lang_t!("msg.failed.get", lang).to_string()).await?;
+            return Ok(());
+        }
+    };
+
+    let removed = {
+        let mut guilddata_mut = GuildDataMut::from(guild_id).await?;
+        let removed = guilddata_mut.dict.remove(key);
+
+        if removed.is_some() {
+            guilddata_mut.update_by(interaction.user.id).await?;
+        }
+        removed
+    };
+
+    // 他の人がすでに削除していた場合
+    if removed.is_none() {
+        send_ephemeral_msg(format_t!("dict.modal.remove.not_found", lang, key)).await?;
+        return Ok(());
+    }
+
+    // 同じページと検索結果で表示し直す
+    let (page, query) = dict::page_state(&interaction.message.components).unwrap_or_default();
+    let (embed, components) = dict::dict_page(ctx, Some(guild_id), lang, &query, page).await?;
+
+    let builder = CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(components),
+    );
+    interaction.create_response(&ctx.http, builder).await?;
+
+    let builder = CreateInteractionResponseFollowup::new().content(format_t!(
+        "dict.modal.remove.deleted",
+        lang,
+        key
+    ));
+    interaction.create_followup(&ctx.http, builder).await?;
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use langrustang::{format_t, lang_t};
+use serenity::all::{
+    ComponentInteraction, ComponentInteractionDataKind, Context, CreateInteractionResponse,
+    CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
+};
+use sonorust_db::{DictChange, GuildDataMut, Permission};
+
+use crate::{
+    commands::dict,
+    crate_extensions::{serenity::SerenityContextExt as _, sonorust_setting::SettingJsonExt as _},
+    errors::SonorustError,
+    Handler,
+};
+
+pub async fn dict_revert(
+    handler: &Handler,
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), SonorustError> {
+    let lang = handler.setting_json.get_bot_lang();
+
+    let guild_id = interaction
+        .guild_id
+        .ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    let send_ephemeral_msg = |content: String| {
+        eq_uilibrium::create_response_msg!(
+            interaction,
+            &ctx.http,
+            content = content,
+            ephemeral = true
+        )
+    };
+
+    // サーバー辞書を編集する権限がなければ返す
+    let is_permitted = ctx
+        .has_permission(guild_id, interaction.user.id, Permission::ManageDict)
+        .await?;
+
+    if !is_permitted {
+        send_ephemeral_msg(lang_t!("msg.no_permission", lang).to_string()).await?;
+        return Ok(());
+    }
+
+    // 選択した変更を取得
+    let change_id = match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values[0].parse().ok(),
+        _ => None,
+    };
+
+    let change = match change_id {
+        Some(change_id) => DictChange::get(guild_id, change_id).await?,
+        None => {
+            log::error!(lang_t!("log.fail_get_data"));
+            send_ephemeral_msg(lang_t!("msg.failed.get", lang).to_string()).await?;
+            return Ok(());
+        }
+    };
+
+    let Some(change) = change else {
+        send_ephemeral_msg(lang_t!("dict.history.not_found", lang).to_string()).await?;
+        return Ok(());
+    };
+
+    let is_reverted = {
+        let mut guilddata_mut = GuildDataMut::from(guild_id).await?;
+
+        // その後に同じ単語が変更されている場合は上書きしない
+        let is_latest = guilddata_mut.dict.get(&change.word) == change.new.as_ref();
+
+        if is_latest {
+            match &change.old {
+                Some(entry) => guilddata_mut
+                    .dict
+                    .insert(change.word.clone(), entry.clone()),
+                None => guilddata_mut.dict.remove(&change.word),
+            };
+
+            guilddata_mut.update_by(interaction.user.id).await?;
+        }
+        is_latest
+    };
+
+    if !is_reverted {
+        send_ephemeral_msg(format_t!("dict.history.conflict", lang, change.word)).await?;
+        return Ok(());
+    }
+
+    // 元に戻したことも履歴に追加されるので表示し直す
+    let (embed, components) = dict::dict_history(ctx, Some(guild_id), lang).await?;
+
+    let builder = CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(components),
+    );
+    interaction.create_response(&ctx.http, builder).await?;
+
+    let builder = CreateInteractionResponseFollowup::new().content(format_t!(
+        "dict.history.reverted",
+        lang,
+        change.id,
+        change.word
+    ));
+    interaction.create_followup(&ctx.http, builder).await?;
+
+    Ok(())
+}
@@ -1,9 +1,15 @@
+pub mod dict_remove;
+pub mod dict_revert;
 pub mod model;
 pub mod server;
+pub mod server_pipeline;
 pub mod speaker;
 pub mod style;
 
+pub use dict_remove::dict_remove;
+pub use dict_revert::dict_revert;
 pub use model::model;
 pub use server::server;
+pub use server_pipeline::server_pipeline;
 pub use speaker::speaker;
 pub use style::style;
@@ -1,9 +1,9 @@
 use langrustang::{format_t, lang_t};
 use serenity::all::{ComponentInteraction, ComponentInteractionDataKind, Context, EditMessage};
-use sonorust_db::GuildDataMut;
+use sonorust_db::{GuildDataMut, Permission};
 
 use crate::{
-    crate_extensions::{serenity::SerenityHttpExt, sonorust_setting::SettingJsonExt},
+    crate_extensions::{serenity::SerenityContextExt, sonorust_setting::SettingJsonExt},
     errors::SonorustError,
     Handler,
 };
@@ -18,20 +18,9 @@ pub async fn server(
     let guild_id = interaction
         .guild_id
         .ok_or_else(|| SonorustError::GuildIdIsNone)?;
-    let inter_member = guild_id.member(&ctx.http, interaction.user.id).await?;
-
-    let is_bot_owner = {
-        let bot_owner_id = ctx.http.get_bot_owner_id().await;
-        interaction.user.id == bot_owner_id
-    };
-
-    let is_admin = {
-        #[allow(deprecated)]
-        match inter_member.permissions(&ctx.cache) {
-            Ok(permissons) => permissons.administrator(),
-            Err(_) => false,
-        }
-    };
+    let is_permitted = ctx
+        .has_permission(guild_id, interaction.user.id, Permission::ManageServer)
+        .await?;
 
     let send_ephemeral_msg = |content: &str| {
         eq_uilibrium::create_response_msg!(
@@ -42,9 +31,9 @@ pub async fn server(
         )
     };
 
-    // 管理者でもbotの所有者でもなければ
-    if !is_admin && !is_bot_owner {
-        send_ephemeral_msg(lang_t!("msg.only_admin", lang)).await?;
+    // サーバー設定を変更する権限がなければ
+    if !is_permitted {
+        send_ephemeral_msg(lang_t!("msg.no_permission", lang)).await?;
         return Ok(());
     }
 
@@ -84,6 +73,25 @@ pub async fn server(
             lang_t!("guild.is_if_long_fastread") => {
                 change_value(&mut guilddata_mut.options.is_if_long_fastread)
             }
+            lang_t!("guild.is_read_vc_chat") => {
+                change_value(&mut guilddata_mut.options.is_read_vc_chat)
+            }
+            lang_t!("guild.is_reread_edited") => {
+                change_value(&mut guilddata_mut.options.is_reread_edited)
+            }
+            lang_t!("guild.is_read_name") => change_value(&mut guilddata_mut.options.is_read_name),
+            lang_t!("guild.is_read_reply") => {
+                change_value(&mut guilddata_mut.options.is_read_reply)
+            }
+            lang_t!("guild.is_romaji_to_kana") => {
+                change_value(&mut guilddata_mut.options.is_romaji_to_kana)
+            }
+            lang_t!("guild.is_skip_spoiler") => {
+                change_value(&mut guilddata_mut.options.is_skip_spoiler)
+            }
+            lang_t!("guild.is_read_url_domain") => {
+                change_value(&mut guilddata_mut.options.is_read_url_domain)
+            }
 
             _ => {
                 log::error!("{}", lang_t!("log.not_implemented_customid"));
@@ -108,6 +116,13 @@ pub async fn server(
         lang_t!("guild.is_entrance_exit_play") => lang_t!("guild.desc.is_entrance_exit_play", lang),
         lang_t!("guild.is_notice_attachment") => lang_t!("guild.desc.is_notice_attachment", lang),
         lang_t!("guild.is_if_long_fastread") => lang_t!("guild.desc.is_if_long_fastread", lang),
+        lang_t!("guild.is_read_vc_chat") => lang_t!("guild.desc.is_read_vc_chat", lang),
+        lang_t!("guild.is_reread_edited") => lang_t!("guild.desc.is_reread_edited", lang),
+        lang_t!("guild.is_read_name") => lang_t!("guild.desc.is_read_name", lang),
+        lang_t!("guild.is_read_reply") => lang_t!("guild.desc.is_read_reply", lang),
+        lang_t!("guild.is_romaji_to_kana") => lang_t!("guild.desc.is_romaji_to_kana", lang),
+        lang_t!("guild.is_skip_spoiler") => lang_t!("guild.desc.is_skip_spoiler", lang),
+        lang_t!("guild.is_read_url_domain") => lang_t!("guild.desc.is_read_url_domain", lang),
 
         _ => {
             log::error!("{}", lang_t!("log.not_implemented_customid"));
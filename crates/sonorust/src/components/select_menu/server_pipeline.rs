@@ -0,0 +1,77 @@
+use langrustang::lang_t;
+use serenity::all::{
+    ComponentInteraction, ComponentInteractionDataKind, Context, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use sonorust_db::{Permission, PipelineStage};
+
+use crate::{
+    commands,
+    crate_extensions::{serenity::SerenityContextExt as _, sonorust_setting::SettingJsonExt as _},
+    errors::SonorustError,
+    Handler,
+};
+
+pub async fn server_pipeline(
+    handler: &Handler,
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), SonorustError> {
+    let lang = handler.setting_json.get_bot_lang();
+
+    let guild_id = interaction
+        .guild_id
+        .ok_or_else(|| SonorustError::GuildIdIsNone)?;
+
+    let send_ephemeral_msg = |content: &str| {
+        eq_uilibrium::create_response_msg!(
+            interaction,
+            &ctx.http,
+            content = content,
+            ephemeral = true
+        )
+    };
+
+    // サーバー設定を変更する権限がなければ返す
+    let is_permitted = ctx
+        .has_permission(guild_id, interaction.user.id, Permission::ManageServer)
+        .await?;
+
+    if !is_permitted {
+        send_ephemeral_msg(lang_t!("msg.no_permission", lang)).await?;
+        return Ok(());
+    }
+
+    // 選択した段階を取得
+    let stage = match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values
+            .first()
+            .and_then(|value| PipelineStage::from_name(value)),
+        _ => None,
+    };
+
+    let Some(stage) = stage else {
+        log::error!(lang_t!("log.fail_get_data"));
+        send_ephemeral_msg(lang_t!("msg.failed.get", lang)).await?;
+        return Ok(());
+    };
+
+    // 選択した段階を変更するボタンを押せるようにして表示し直す
+    let (embed, components) = commands::server::server_with_stage(
+        ctx,
+        Some(guild_id),
+        interaction.user.id,
+        Some(stage),
+        lang,
+    )
+    .await?;
+
+    let builder = CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(components),
+    );
+    interaction.create_response(&ctx.http, builder).await?;
+
+    Ok(())
+}
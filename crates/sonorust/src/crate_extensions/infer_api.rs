@@ -1,13 +1,16 @@
 use std::{
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
     time::Duration,
 };
 
-use crate::{errors::SonorustError, Handler};
+use crate::{errors::SonorustError, Handler, QueueItem};
 use either::Either;
 use infer_api::{Sbv2PythonClient, Sbv2PythonInferParam, Sbv2RustClient};
 use langrustang::lang_t;
-use serenity::all::{ChannelId, Context, GuildId, UserId};
+use serenity::all::{ChannelId, Context, GuildId, MessageId, UserId};
 use songbird::input::Input;
 use sonorust_db::{GuildData, UserData};
 use sonorust_setting::{InferUse, SettingJson};
@@ -25,6 +28,7 @@ pub trait InferApiExt {
         setting_json: &ArcRwLock<SettingJson>,
     ) -> Result<Vec<u8>, SonorustError>;
 
+    #[allow(clippy::too_many_arguments)]
     async fn play_on_vc(
         &self,
         handler: &Handler,
@@ -32,6 +36,7 @@ pub trait InferApiExt {
         guild_id: Option<GuildId>,
         channel_id: ChannelId,
         user_id: UserId,
+        message_id: Option<MessageId>,
         play_content: &str,
     ) -> Result<(), SonorustError>;
 }
@@ -82,6 +87,7 @@ impl InferApiExt for TokioRwLock<Either<Sbv2PythonClient, Sbv2RustClient>> {
         guild_id: Option<GuildId>,
         channel_id: ChannelId,
         user_id: UserId,
+        message_id: Option<MessageId>,
         play_content: &str,
     ) -> Result<(), SonorustError> {
         // サーバー上でない場合何もしない
@@ -133,10 +139,36 @@ impl InferApiExt for TokioRwLock<Either<Sbv2PythonClient, Sbv2RustClient>> {
             userdata.length = 0.5;
         }
 
+        // 生成中に削除・編集されたか判別できるように、生成中のメッセージとして登録する
+        static NEXT_PENDING_NUM: AtomicU64 = AtomicU64::new(0);
+
+        let pending_key =
+            message_id.map(|id| (id, NEXT_PENDING_NUM.fetch_add(1, Ordering::Relaxed)));
+
+        if let Some(key) = pending_key {
+            handler
+                .pending_messages
+                .with_write(|lock| lock.entry(guild_id).or_default().insert(key));
+        }
+
         let audio_data = handler
             .infer_client
             .infer_from_user(play_content, userdata, &handler.setting_json)
-            .await?;
+            .await;
+
+        // 登録が外されていた場合は生成中に削除・編集されているため読み上げない
+        if let Some(key) = pending_key {
+            let is_pending = handler.pending_messages.with_write(|lock| {
+                lock.get_mut(&guild_id)
+                    .is_some_and(|pending| pending.remove(&key))
+            });
+
+            if !is_pending {
+                return Ok(());
+            }
+        }
+
+        let audio_data = audio_data?;
         // ------
 
         // そのチャンネルのqueueに音声データを追加する
@@ -147,7 +179,7 @@ impl InferApiExt for TokioRwLock<Either<Sbv2PythonClient, Sbv2RustClient>> {
                 return Ok(());
             };
 
-            read_ch_queue.push_front(audio_data);
+            read_ch_queue.push_front(QueueItem::new(message_id, audio_data));
 
             // もし再生待ちが1つだけなら再生に移る
             // (下の方ではqueueがなくなるまで繰り返すため)
@@ -158,21 +190,23 @@ impl InferApiExt for TokioRwLock<Either<Sbv2PythonClient, Sbv2RustClient>> {
 
         let infer_use = handler.setting_json.with_read(|lock| lock.infer_use);
         loop {
-            let voice_data = {
+            let (voice_data, skip_notify) = {
                 let mut channel_queues = handler.channel_queues.write().unwrap();
                 let Some(read_ch_queue) = channel_queues.get_mut(&guild_id) else {
                     log::error!(lang_t!("log.fail_ch_queue"));
                     return Ok(());
                 };
 
-                let mut voice_data = vec![];
-
                 match read_ch_queue.back_mut() {
-                    Some(data) => std::mem::swap(&mut voice_data, data),
+                    Some(item) => {
+                        item.is_playing = true;
+                        (
+                            std::mem::take(&mut item.audio_data),
+                            item.skip_notify.clone(),
+                        )
+                    }
                     None => return Ok(()),
                 }
-
-                voice_data
             };
 
             // 再生時間を求める
@@ -183,7 +217,7 @@ impl InferApiExt for TokioRwLock<Either<Sbv2PythonClient, Sbv2RustClient>> {
 
             // 音声を VC で作成
             let input = Input::from(voice_data);
-            {
+            let track_handle = {
                 let mut handler = handler_lock.lock().await;
 
                 let track_handle = handler.play_input(input);
@@ -196,11 +230,23 @@ impl InferApiExt for TokioRwLock<Either<Sbv2PythonClient, Sbv2RustClient>> {
                 if let Err(err) = set_volume {
                     log::error!("{}: {err}", lang_t!("log.fail_adj_vol"))
                 }
-            }
 
-            // その音声の再生時間だけスリープする
+                track_handle
+            };
+
+            // 削除されたときに途中で止められるようにハンドルを保存しておく
+            handler.channel_queues.with_write(|lock| {
+                if let Some(item) = lock.get_mut(&guild_id).and_then(|queue| queue.back_mut()) {
+                    item.track_handle = Some(track_handle);
+                }
+            });
+
+            // その音声の再生時間だけスリープする (途中で止められた場合はすぐ次に移る)
             let duration = Duration::from_secs_f64(voice_playtime);
-            tokio::time::sleep(duration).await;
+            tokio::select! {
+                _ = tokio::time::sleep(duration) => (),
+                _ = skip_notify.notified() => (),
+            }
 
             {
                 let mut channel_queues = handler.channel_queues.write().unwrap();
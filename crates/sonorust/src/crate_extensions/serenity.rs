@@ -1,19 +1,134 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
-use serenity::all::{Http, UserId};
+use serenity::all::{CacheHttp, Context, GuildId, Http, Member, User, UserId};
+use sonorust_db::{GuildData, Permission};
+
+use crate::errors::SonorustError;
 
 pub trait SerenityHttpExt {
     async fn get_bot_owner_id(&self) -> UserId;
 }
 
 impl SerenityHttpExt for Arc<Http> {
+    /// bot の所有者の ID (取得できた場合は保存して次からは API を呼ばない)
     async fn get_bot_owner_id(&self) -> UserId {
+        static OWNER_ID: OnceLock<UserId> = OnceLock::new();
+
+        if let Some(owner_id) = OWNER_ID.get() {
+            return *owner_id;
+        }
+
         match self.get_current_application_info().await {
             Ok(info) => match info.owner {
-                Some(owner) => owner.id,
+                Some(owner) => *OWNER_ID.get_or_init(|| owner.id),
                 None => UserId::new(1),
             },
             Err(_) => UserId::new(1),
         }
     }
 }
+
+pub trait SerenityUserExt {
+    /// サーバーでの表示名を取得する (ニックネーム -> 表示名 -> ユーザー名)
+    async fn name_in_guild(&self, cache_http: impl CacheHttp, guild_id: GuildId) -> String;
+}
+
+impl SerenityUserExt for User {
+    async fn name_in_guild(&self, cache_http: impl CacheHttp, guild_id: GuildId) -> String {
+        self.nick_in(cache_http, guild_id).await.unwrap_or_else(|| {
+            self.global_name
+                .clone()
+                .unwrap_or_else(|| self.name.clone())
+        })
+    }
+}
+
+pub trait SerenityContextExt {
+    async fn is_admin_or_owner(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<bool, SonorustError>;
+
+    async fn has_permission(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        permission: Permission,
+    ) -> Result<bool, SonorustError>;
+}
+
+impl SerenityContextExt for Context {
+    /// サーバーの管理者か bot の所有者か
+    async fn is_admin_or_owner(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<bool, SonorustError> {
+        // サーバーの所有者はキャッシュから確認する
+        let is_guild_owner = self
+            .cache
+            .guild(guild_id)
+            .is_some_and(|guild| guild.owner_id == user_id);
+
+        if is_guild_owner {
+            return Ok(true);
+        }
+
+        let member = member_of(self, guild_id, user_id).await?;
+
+        let is_admin = {
+            #[allow(deprecated)]
+            match member.permissions(&self.cache) {
+                Ok(permissons) => permissons.administrator(),
+                Err(_) => false,
+            }
+        };
+
+        if is_admin {
+            return Ok(true);
+        }
+
+        let app_owner_id = self.http.get_bot_owner_id().await;
+        Ok(app_owner_id == user_id)
+    }
+
+    /// サーバーで設定された権限を持っているか (管理者と bot の所有者は常に許可)
+    async fn has_permission(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        permission: Permission,
+    ) -> Result<bool, SonorustError> {
+        let guilddata = GuildData::from(guild_id).await?;
+
+        // 誰でも使える場合はメンバーを取得しない
+        if guilddata.is_permitted(permission, &[]) {
+            return Ok(true);
+        }
+
+        let member = member_of(self, guild_id, user_id).await?;
+        if guilddata.is_permitted(permission, &member.roles) {
+            return Ok(true);
+        }
+
+        self.is_admin_or_owner(guild_id, user_id).await
+    }
+}
+
+/// メンバーを取得する (キャッシュになければ API から取得する)
+async fn member_of(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<Member, SonorustError> {
+    let cached = ctx
+        .cache
+        .guild(guild_id)
+        .and_then(|guild| guild.members.get(&user_id).cloned());
+
+    match cached {
+        Some(member) => Ok(member),
+        None => Ok(guild_id.member(ctx, user_id).await?),
+    }
+}
@@ -5,10 +5,15 @@ mod components;
 mod crate_extensions;
 mod errors;
 mod registers;
+mod text_replace;
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::RwLock;
-use std::{path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate_extensions::rwlock::RwLockExt;
 use crate_extensions::sonorust_setting::SettingJsonExt;
@@ -19,15 +24,16 @@ use infer_api::{Sbv2PythonClient, Sbv2RustClient, Sbv2RustDownloads, Sbv2RustErr
 use langrustang::lang_t;
 use serenity::all::GatewayError::DisallowedGatewayIntents;
 use serenity::all::{
-    ChannelId, Colour, Context, CreateEmbed, GuildId, Interaction, Message, Ready, VoiceState,
+    ChannelId, Colour, Context, CreateEmbed, GuildId, Interaction, Message, MessageId,
+    MessageUpdateEvent, Ready, UserId, VoiceState,
 };
 use serenity::{
     all::{EventHandler, GatewayIntents},
     async_trait, Client,
 };
-use songbird::SerenityInit;
+use songbird::{tracks::TrackHandle, SerenityInit};
 use sonorust_setting::{InferUse, SettingJson};
-use tokio::sync::RwLock as TokioRwLock;
+use tokio::sync::{Notify, RwLock as TokioRwLock};
 
 type ArcRwLock<T> = Arc<RwLock<T>>;
 
@@ -35,7 +41,36 @@ struct Handler {
     pub setting_json: ArcRwLock<SettingJson>,
     pub infer_client: Arc<TokioRwLock<Either<Sbv2PythonClient, Sbv2RustClient>>>,
     pub read_channels: ArcRwLock<HashMap<GuildId, HashSet<ChannelId>>>,
-    pub channel_queues: ArcRwLock<HashMap<GuildId, VecDeque<Vec<u8>>>>,
+    pub channel_queues: ArcRwLock<HashMap<GuildId, VecDeque<QueueItem>>>,
+
+    /// 音声を生成中のメッセージ (MessageId, 生成ごとに割り振る番号)
+    pub pending_messages: ArcRwLock<HashMap<GuildId, HashSet<(MessageId, u64)>>>,
+
+    /// 最後に読み上げたメッセージの発言者と時間 (名前の読み上げ用)
+    pub last_speakers: ArcRwLock<HashMap<GuildId, (UserId, Instant)>>,
+}
+
+/// 読み上げ queue に入れる音声データ
+struct QueueItem {
+    pub message_id: Option<MessageId>,
+    pub audio_data: Vec<u8>,
+    pub is_playing: bool,
+
+    /// 再生中の場合、途中で止めるためのハンドル
+    pub track_handle: Option<TrackHandle>,
+    pub skip_notify: Arc<Notify>,
+}
+
+impl QueueItem {
+    pub fn new(message_id: Option<MessageId>, audio_data: Vec<u8>) -> Self {
+        Self {
+            message_id,
+            audio_data,
+            is_playing: false,
+            track_handle: None,
+            skip_notify: Arc::new(Notify::new()),
+        }
+    }
 }
 
 #[async_trait]
@@ -66,6 +101,40 @@ impl EventHandler for Handler {
         };
     }
 
+    async fn message_update(
+        &self,
+        ctx: Context,
+        _old_if_available: Option<Message>,
+        new: Option<Message>,
+        event: MessageUpdateEvent,
+    ) {
+        if let Err(err) = registers::message_update(self, &ctx, &new, &event).await {
+            log::error!("Error on message_update: {err}")
+        }
+    }
+
+    async fn message_delete(
+        &self,
+        _ctx: Context,
+        _channel_id: ChannelId,
+        deleted_message_id: MessageId,
+        guild_id: Option<GuildId>,
+    ) {
+        registers::message_delete(self, guild_id, deleted_message_id);
+    }
+
+    async fn message_delete_bulk(
+        &self,
+        _ctx: Context,
+        _channel_id: ChannelId,
+        multiple_deleted_messages_ids: Vec<MessageId>,
+        guild_id: Option<GuildId>,
+    ) {
+        for message_id in multiple_deleted_messages_ids {
+            registers::message_delete(self, guild_id, message_id);
+        }
+    }
+
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         match &interaction {
             Interaction::Command(inter) => {
@@ -156,10 +225,28 @@ async fn main() {
         .await
         .expect("Failed init json");
 
-    // カタカナ読み辞書の初期化
-    EngToKana::download_and_init_dic("appdata/downloads")
-        .await
-        .expect("Failed init engtokana dict");
+    // カタカナ読み辞書の初期化 (設定されている場合のみ bep-eng.dic をダウンロードする)
+    // (ダウンロードに失敗した場合は同梱の辞書のみで読む)
+    let engtokana_overlay = "appdata/engtokana.dic";
+    let report = match setting_json.is_download_engtokana_dic {
+        true => {
+            match EngToKana::download_and_init_dic(&downloads_folder, engtokana_overlay).await {
+                Ok(report) => report,
+                Err(err) => {
+                    log::warn!("Failed download engtokana dict, using bundled dict: {err}");
+                    EngToKana::init_dic(&downloads_folder, engtokana_overlay).await
+                }
+            }
+        }
+        false => EngToKana::init_dic(&downloads_folder, engtokana_overlay).await,
+    };
+    log::info!(
+        "Loaded engtokana dict: {} words ({} malformed lines)",
+        report.entries,
+        report.malformed.len()
+    );
+    EngToKana::set_spell_max_len(setting_json.engtokana_spell_max_len as usize);
+    EngToKana::set_acronym_max_len(setting_json.engtokana_acronym_max_len as usize);
 
     // データベースの初期化
     sonorust_db::init_database("appdata/database.db")
@@ -255,6 +342,8 @@ async fn main() {
     let infer_client = Arc::new(TokioRwLock::new(infer_client));
     let read_channels = Arc::new(RwLock::new(HashMap::new()));
     let channel_queues = Arc::new(RwLock::new(HashMap::new()));
+    let pending_messages = Arc::new(RwLock::new(HashMap::new()));
+    let last_speakers = Arc::new(RwLock::new(HashMap::new()));
 
     loop {
         let bot_token = setting_json.with_read(|lock| lock.bot_token.clone());
@@ -265,6 +354,8 @@ async fn main() {
                 infer_client: infer_client.clone(),
                 read_channels: read_channels.clone(),
                 channel_queues: channel_queues.clone(),
+                pending_messages: pending_messages.clone(),
+                last_speakers: last_speakers.clone(),
             })
             .register_songbird()
             .await
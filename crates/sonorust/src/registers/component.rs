@@ -26,6 +26,12 @@ pub async fn component(
         lang_t!("customid.select.style") => {
             components::select_menu::style(handler, ctx, interaction).await?
         }
+        lang_t!("customid.dict.select_remove") => {
+            components::select_menu::dict_remove(handler, ctx, interaction).await?
+        }
+        lang_t!("customid.dict.select_revert") => {
+            components::select_menu::dict_revert(handler, ctx, interaction).await?
+        }
 
         lang_t!("customid.page.model.forward") => {
             components::button::move_page(handler, ctx, interaction, custom_id).await?
@@ -46,9 +52,31 @@ pub async fn component(
             components::button::move_page(handler, ctx, interaction, custom_id).await?
         }
 
+        lang_t!("customid.page.dict.forward") => {
+            components::button::dict_move_page(handler, ctx, interaction, custom_id).await?
+        }
+        lang_t!("customid.page.dict.back") => {
+            components::button::dict_move_page(handler, ctx, interaction, custom_id).await?
+        }
+
         lang_t!("customid.change_server_settings") => {
             components::select_menu::server(handler, ctx, interaction).await?
         }
+        lang_t!("customid.server.pipeline_select") => {
+            components::select_menu::server_pipeline(handler, ctx, interaction).await?
+        }
+        lang_t!("customid.server.pipeline_toggle") => {
+            components::button::server_pipeline(handler, ctx, interaction, custom_id).await?
+        }
+        lang_t!("customid.server.pipeline_up") => {
+            components::button::server_pipeline(handler, ctx, interaction, custom_id).await?
+        }
+        lang_t!("customid.server.pipeline_down") => {
+            components::button::server_pipeline(handler, ctx, interaction, custom_id).await?
+        }
+        lang_t!("customid.server.pipeline_reset") => {
+            components::button::server_pipeline_reset(handler, ctx, interaction).await?
+        }
 
         lang_t!("customid.dict.add") => {
             components::button::dict_add(handler, ctx, interaction).await?
@@ -56,6 +84,18 @@ pub async fn component(
         lang_t!("customid.dict.remove") => {
             components::button::dict_remove(handler, ctx, interaction).await?
         }
+        lang_t!("customid.globaldict.add") => {
+            components::button::globaldict_add(handler, ctx, interaction).await?
+        }
+        lang_t!("customid.globaldict.remove") => {
+            components::button::globaldict_remove(handler, ctx, interaction).await?
+        }
+        lang_t!("customid.mydict.add") => {
+            components::button::mydict_add(handler, ctx, interaction).await?
+        }
+        lang_t!("customid.mydict.remove") => {
+            components::button::mydict_remove(handler, ctx, interaction).await?
+        }
 
         _ => {
             log::error!(lang_t!("log.not_implemented_customid"));
@@ -1,22 +1,24 @@
 use std::{
-    collections::HashMap,
-    sync::{LazyLock, OnceLock},
-    time::Instant,
+    sync::OnceLock,
+    time::{Duration, Instant},
 };
 
 use either::Either;
-use engtokana::EngToKana;
 use langrustang::{format_t, lang_t};
-use regex::Regex;
-use serenity::all::{Context, CreateMessage, EditMessage, Message};
-use sonorust_db::GuildData;
+use serenity::all::{Context, CreateMessage, EditMessage, GuildId, Message, MessageType, User};
+use sonorust_db::{GlobalDict, GuildData, Permission, UserData};
 
 use crate::{
-    commands,
+    _langrustang_autogen::Lang,
+    commands::{self, dict::format::DictFormat},
     crate_extensions::{
-        infer_api::InferApiExt, rwlock::RwLockExt, sonorust_setting::SettingJsonExt,
+        infer_api::InferApiExt,
+        rwlock::RwLockExt,
+        serenity::{SerenityContextExt, SerenityUserExt},
+        sonorust_setting::SettingJsonExt,
     },
     errors::SonorustError,
+    text_replace::{PipelineContext, TextReplace},
     Handler,
 };
 
@@ -139,6 +141,7 @@ async fn command_processing(
                     msg.guild_id,
                     msg.channel_id,
                     msg.author.id,
+                    None,
                     lang_t!("join.connected", lang),
                 )
                 .await?;
@@ -208,6 +211,15 @@ async fn command_processing(
             let content = commands::length(msg.author.id, length, lang).await?;
             msg.channel_id.say(&ctx.http, content).await?;
         }
+        "read_name" => {
+            debug_log();
+
+            // 引数がなければリセット
+            let read_name = msg.content.split_once(" ").map(|(_, rest)| rest);
+
+            let content = commands::read_name(msg.author.id, read_name, lang).await?;
+            msg.channel_id.say(&ctx.http, content).await?;
+        }
         "wav" => {
             debug_log();
 
@@ -231,10 +243,132 @@ async fn command_processing(
                 }
             };
         }
+        "preview" => {
+            debug_log();
+
+            // 確認する文章を取得
+            let Some((_, text)) = msg.content.split_once(" ") else {
+                msg.channel_id
+                    .say(&ctx.http, format_t!("preview.usage", lang, prefix))
+                    .await?;
+                return Ok(());
+            };
+
+            let embed =
+                commands::preview(handler, ctx, msg.guild_id, msg.author.id, text, lang).await?;
+            eq_uilibrium::send_msg!(msg.channel_id, &ctx.http, embed = embed).await?;
+        }
         "dict" => {
             debug_log();
 
-            let (embed, components) = commands::dict(ctx, msg.guild_id, lang).await?;
+            match command_rest.first().map(|s| **s) {
+                Some("export") => {
+                    // 形式が指定されていなければ json で書き出す
+                    let format = match command_rest.get(1) {
+                        Some(name) => DictFormat::from_name(name),
+                        None => Some(DictFormat::Json),
+                    };
+
+                    let Some(format) = format else {
+                        msg.channel_id
+                            .say(&ctx.http, format_t!("dict.export.usage", lang, prefix))
+                            .await?;
+                        return Ok(());
+                    };
+
+                    let (attachment, content) =
+                        commands::dict::dict_export(msg.guild_id, format, lang).await?;
+                    eq_uilibrium::send_msg!(
+                        msg.channel_id,
+                        &ctx.http,
+                        content = content,
+                        add_file = attachment
+                    )
+                    .await?;
+                }
+                Some("search") => {
+                    // 空白を含む文字列も検索できるように残りをすべてつなげる
+                    let query = command_rest[1..].iter().map(|s| **s).collect::<Vec<_>>();
+                    let query = query.join(" ");
+
+                    if query.is_empty() {
+                        msg.channel_id
+                            .say(&ctx.http, format_t!("dict.search.usage", lang, prefix))
+                            .await?;
+                        return Ok(());
+                    }
+
+                    let (embed, components) =
+                        commands::dict::dict_page(ctx, msg.guild_id, lang, &query, 0).await?;
+                    eq_uilibrium::send_msg!(
+                        msg.channel_id,
+                        &ctx.http,
+                        embed = embed,
+                        components = components
+                    )
+                    .await?;
+                }
+                Some("history") => {
+                    let (embed, components) =
+                        commands::dict::dict_history(ctx, msg.guild_id, lang).await?;
+                    eq_uilibrium::send_msg!(
+                        msg.channel_id,
+                        &ctx.http,
+                        embed = embed,
+                        components = components
+                    )
+                    .await?;
+                }
+                Some("import") => {
+                    // メッセージに添付されたファイルを読み込む
+                    let Some(attachment) = msg.attachments.first() else {
+                        msg.channel_id
+                            .say(&ctx.http, format_t!("dict.import.usage", lang, prefix))
+                            .await?;
+                        return Ok(());
+                    };
+
+                    let is_replace = command_rest.get(1).is_some_and(|s| **s == "replace");
+
+                    let content = commands::dict::dict_import(
+                        ctx,
+                        msg.guild_id,
+                        msg.author.id,
+                        attachment,
+                        is_replace,
+                        lang,
+                    )
+                    .await?;
+                    msg.channel_id.say(&ctx.http, content).await?;
+                }
+                _ => {
+                    let (embed, components) = commands::dict(ctx, msg.guild_id, lang).await?;
+                    eq_uilibrium::send_msg!(
+                        msg.channel_id,
+                        &ctx.http,
+                        embed = embed,
+                        components = components
+                    )
+                    .await?;
+                }
+            }
+        }
+        "globaldict" => {
+            debug_log();
+
+            let (embed, components) = commands::globaldict(ctx, msg.author.id, lang).await?;
+            eq_uilibrium::send_msg!(
+                msg.channel_id,
+                &ctx.http,
+                embed = embed,
+                components = components
+            )
+            .await?;
+        }
+        "mydict" => {
+            debug_log();
+
+            let (embed, components) = commands::mydict(&msg.author, lang).await?;
             eq_uilibrium::send_msg!(
                 msg.channel_id,
                 &ctx.http,
@@ -289,6 +423,55 @@ async fn command_processing(
                 .send_message(&ctx.http, create_message)
                 .await?;
         }
+        "ignore" => {
+            debug_log();
+
+            // メンションされたユーザーがいれば登録 or 解除
+            let target_user = msg.mentions.first().map(|user| user.id);
+
+            let (embed, text) =
+                commands::ignore(ctx, msg.guild_id, msg.author.id, lang, target_user).await?;
+
+            let mut create_message = CreateMessage::new();
+
+            if let Some(embed) = embed {
+                create_message = create_message.add_embed(embed);
+            }
+
+            if let Some(text) = text {
+                create_message = create_message.content(text);
+            }
+
+            msg.channel_id
+                .send_message(&ctx.http, create_message)
+                .await?;
+        }
+        "permission" => {
+            debug_log();
+
+            let (embed, text) =
+                commands::permission(ctx, msg.guild_id, msg.author.id, lang, None).await?;
+
+            let mut create_message = CreateMessage::new();
+
+            if let Some(embed) = embed {
+                create_message = create_message.add_embed(embed);
+            }
+
+            if let Some(text) = text {
+                create_message = create_message.content(text);
+            }
+
+            msg.channel_id
+                .send_message(&ctx.http, create_message)
+                .await?;
+        }
+        "read_me" => {
+            debug_log();
+
+            let content = commands::read_me(msg.author.id, lang).await?;
+            msg.channel_id.say(&ctx.http, content).await?;
+        }
         "read_add" => {
             debug_log();
 
@@ -318,7 +501,7 @@ async fn command_processing(
 }
 
 // コマンド以外だった時の処理 (主にvcで音声再生)
-async fn other_processing(
+pub(super) async fn other_processing(
     handler: &Handler,
     ctx: &Context,
     msg: &Message,
@@ -349,31 +532,38 @@ async fn other_processing(
 
     let guilddata = GuildData::from(guild_id).await?;
 
-    let lang = handler.setting_json.get_bot_lang();
-
-    // 読み上げ用に文字を置換する
-    let mut text_replace = TextReplace::new(&msg.content);
-
-    text_replace.remove_err();
-    text_replace.remove_codeblock();
-    text_replace.remove_url();
-    text_replace.remove_discord_obj();
+    // 読み上げないユーザーに登録されているか、本人が読み上げを無効にしている場合無視
+    if guilddata.ignored_users.contains(&msg.author.id) {
+        return Ok(());
+    }
 
-    text_replace.replace_from_guilddict(&guilddata);
+    let userdata = UserData::from(msg.author.id).await?;
+    if userdata.is_read_disabled {
+        return Ok(());
+    }
 
-    // 日本語の時のみ英語を日本語読みに変換
-    {
-        use crate::_langrustang_autogen::Lang::*;
+    // 読み上げられる権限がない場合無視
+    let is_permitted = ctx
+        .has_permission(guild_id, msg.author.id, Permission::BeRead)
+        .await?;
 
-        match lang {
-            Ja => text_replace.eng_to_kana(),
-            _ => (),
-        }
+    if !is_permitted {
+        return Ok(());
     }
 
-    text_replace.remove_emoji();
+    let lang = handler.setting_json.get_bot_lang();
 
-    let replaced_text = text_replace.as_string();
+    // 読み上げ用に文字を置換する
+    let replaced_text = replace_for_read(
+        handler,
+        ctx,
+        guild_id,
+        &guilddata,
+        &userdata,
+        &msg.content,
+        lang,
+    )
+    .await?;
 
     // read_limit よりも長い場合はその長さに制限する
     let read_limit = handler.setting_json.with_read(|lock| lock.read_limit);
@@ -382,8 +572,59 @@ async fn other_processing(
         None => replaced_text,
     };
 
+    // 設定で ON になっていて返信なら返信先を読み上げる
+    let content = match (guilddata.options.is_read_reply, reply_target(msg)) {
+        (true, Some(reply_msg)) => {
+            let excerpt_limit = handler
+                .setting_json
+                .with_read(|lock| lock.reply_excerpt_limit);
+
+            let reply_name = read_name_of(ctx, guild_id, &reply_msg.author, &guilddata, lang)
+                .await?
+                .unwrap_or_default();
+
+            // 返信先の内容を excerpt_limit 文字まで読む (0 なら読まない)
+            let excerpt = match excerpt_limit == 0 || reply_msg.content.trim().is_empty() {
+                true => String::new(),
+                false => {
+                    let reply_userdata = UserData::from(reply_msg.author.id).await?;
+                    let text = replace_for_read(
+                        handler,
+                        ctx,
+                        guild_id,
+                        &guilddata,
+                        &reply_userdata,
+                        &reply_msg.content,
+                        lang,
+                    )
+                    .await?;
+                    text.chars().take(excerpt_limit as usize).collect()
+                }
+            };
+
+            match excerpt.is_empty() {
+                true => format_t!("text.reply_to", lang, reply_name, content),
+                false => format_t!("text.reply_to_excerpt", lang, reply_name, excerpt, content),
+            }
+        }
+        _ => content,
+    };
+
+    // 設定で ON になっていて発言者が変わっていたら最初に名前を読み上げる
+    let mut speaker_name = match guilddata.options.is_read_name {
+        true => speaker_name_if_changed(handler, ctx, guild_id, msg, &guilddata, lang).await?,
+        false => None,
+    };
+
+    let mut with_speaker_name = |text: &str| match speaker_name.take() {
+        Some(name) => format_t!("text.speaker_name", lang, name, text),
+        None => text.to_string(),
+    };
+
     // 設定で ON になっていて添付ファイルがあるなら添付ファイルがあることを知らせる
     if !msg.attachments.is_empty() && guilddata.options.is_notice_attachment {
+        let content = with_speaker_name(lang_t!("msg.attachments", lang));
+
         handler
             .infer_client
             .play_on_vc(
@@ -392,11 +633,14 @@ async fn other_processing(
                 msg.guild_id,
                 msg.channel_id,
                 msg.author.id,
-                lang_t!("msg.attachments", lang),
+                Some(msg.id),
+                &content,
             )
             .await?;
     }
 
+    let content = with_speaker_name(&content);
+
     handler
         .infer_client
         .play_on_vc(
@@ -405,6 +649,7 @@ async fn other_processing(
             msg.guild_id,
             msg.channel_id,
             msg.author.id,
+            Some(msg.id),
             &content,
         )
         .await?;
@@ -412,95 +657,101 @@ async fn other_processing(
     Ok(())
 }
 
-#[derive(Debug, Clone)]
-pub struct TextReplace {
-    text: String,
-}
-
-impl TextReplace {
-    pub fn new<S>(text: S) -> Self
-    where
-        S: Into<String>,
-    {
-        Self { text: text.into() }
-    }
-
-    pub fn as_string(self) -> String {
-        self.text
-    }
-
-    pub fn remove_codeblock(&mut self) {
-        // ``` が含まれていた場合全体をコードブロックと読む
-        if self.text.contains("```") {
-            self.text = "コードブロック".to_string();
-            return;
+/// 前回の発言者と違うか、前回から一定時間以上経っていたら読み上げる名前を返す
+async fn speaker_name_if_changed(
+    handler: &Handler,
+    ctx: &Context,
+    guild_id: GuildId,
+    msg: &Message,
+    guilddata: &GuildData,
+    lang: Lang,
+) -> Result<Option<String>, SonorustError> {
+    let interval = handler
+        .setting_json
+        .with_read(|lock| Duration::from_secs(lock.read_name_interval));
+
+    let now = Instant::now();
+    let is_changed = handler.last_speakers.with_write(|lock| {
+        match lock.insert(guild_id, (msg.author.id, now)) {
+            Some((user_id, time)) => user_id != msg.author.id || now - time >= interval,
+            None => true,
         }
+    });
 
-        let re = Regex::new(r"`.*?`").unwrap();
-        self.text = re.replace_all(&self.text, "コード").to_string()
+    if !is_changed {
+        return Ok(None);
     }
 
-    pub fn remove_url(&mut self) {
-        let re = Regex::new(r"https?://[\w/:%#\$&\?\(\)~\.=\+\-]+").unwrap();
-        self.text = re.replace_all(&self.text, "URL").to_string()
-    }
+    read_name_of(ctx, guild_id, &msg.author, guilddata, lang).await
+}
 
-    /// チャンネルやメンション、カスタム絵文字などの置換
-    pub fn remove_discord_obj(&mut self) {
-        let re = Regex::new(r"<.*?>").unwrap();
-        self.text = re.replace_all(&self.text, "").to_string()
-    }
+/// ユーザーの名前を読み上げる形で取得する
+async fn read_name_of(
+    ctx: &Context,
+    guild_id: GuildId,
+    user: &User,
+    guilddata: &GuildData,
+    lang: Lang,
+) -> Result<Option<String>, SonorustError> {
+    // 読み方が設定されていなければサーバーでの表示名を使う
+    let userdata = UserData::from(user.id).await?;
+    let name = match userdata.read_name {
+        Some(name) => name,
+        None => user.name_in_guild(ctx, guild_id).await,
+    };
 
-    pub fn remove_emoji(&mut self) {
-        let re = Regex::new(r"[^\p{L}\p{N}\p{Pd}\p{Sm}\p{Sc}]").unwrap();
-        self.text = re.replace_all(&self.text, "").to_string()
-    }
+    let global_dict = GlobalDict::get().await?;
 
-    /// 指定したサーバー辞書をもとに置換する
-    pub fn replace_from_guilddict(&mut self, guilddata: &GuildData) {
-        let map = &guilddata.dict;
+    let mut text_replace = TextReplace::new(name);
+    text_replace.replace_from_globaldict(&global_dict);
+    text_replace.replace_from_guilddict(guilddata);
 
-        self.replace_from_hashmap(map);
+    if let Lang::Ja = lang {
+        text_replace.eng_to_kana(guilddata.options.is_romaji_to_kana);
     }
 
-    /// HashMap をもとに HashMap の Key を Value に置換する
-    pub fn replace_from_hashmap(&mut self, map: &HashMap<String, String>) {
-        let mut replace_texts = HashMap::new();
-
-        for (i, (before, after)) in map.iter().enumerate() {
-            let mark = format!("{{|{}|}}", i);
-
-            self.text = self.text.replace(before, &mark).to_string();
-            replace_texts.insert(mark, after);
-        }
+    text_replace.remove_emoji();
 
-        for (before, after) in replace_texts {
-            self.text = self.text.replace(&before, after)
-        }
-    }
+    let name = text_replace.as_string();
+    Ok((!name.is_empty()).then_some(name))
+}
 
-    /// 英語をカタカナ読みに変換する
-    pub fn eng_to_kana(&mut self) {
-        self.text = EngToKana::convert_all(&self.text);
+/// 返信の場合は返信先のメッセージを返す
+fn reply_target(msg: &Message) -> Option<&Message> {
+    match msg.kind {
+        MessageType::InlineReply => msg.referenced_message.as_deref(),
+        _ => None,
     }
+}
 
-    // ~ から始まるとなぜかエラーをはいたりするため修正
-    // っーーー や ッーーー を っ に修正
-    pub fn remove_err(&mut self) {
-        self.text = self.text.replace("~", "-").replace("～", "ー");
+/// 読み上げ用に文字を置換する
+async fn replace_for_read(
+    handler: &Handler,
+    ctx: &Context,
+    guild_id: GuildId,
+    guilddata: &GuildData,
+    userdata: &UserData,
+    text: &str,
+    lang: Lang,
+) -> Result<String, SonorustError> {
+    let global_dict = GlobalDict::get().await?;
+    let emoji_repeat_limit = handler
+        .setting_json
+        .with_read(|lock| lock.emoji_repeat_limit);
+
+    let context = PipelineContext {
+        cache: &ctx.cache,
+        guild_id,
+        guilddata,
+        userdata,
+        global_dict: &global_dict,
+        lang,
+        emoji_repeat_limit,
+    };
 
-        static RE: LazyLock<Regex> =
-            LazyLock::new(|| Regex::new(r"(ッ|ｯ|っ)ー+").expect("Regex Failed"));
-        self.text = RE.replace_all(&self.text, "$1").to_string();
+    // サーバーで設定された順番で置換する
+    let mut text_replace = TextReplace::new(text);
+    text_replace.run_pipeline(&context, |_, _| ());
 
-        if self.text.starts_with("ー") {
-            if let Some(s) = self.text.strip_prefix("ー") {
-                self.text = s.to_string();
-            };
-        }
-
-        if self.text.is_empty() {
-            self.text = String::from("-");
-        }
-    }
+    Ok(text_replace.as_string())
 }
@@ -0,0 +1,74 @@
+use serenity::all::{GuildId, MessageId};
+
+use crate::{crate_extensions::rwlock::RwLockExt, Handler};
+
+pub fn message_delete(handler: &Handler, guild_id: Option<GuildId>, message_id: MessageId) {
+    // サーバー上でない場合何もしない
+    let Some(guild_id) = guild_id else {
+        return;
+    };
+
+    // まだ読み上げていない場合は取り消し、読み上げ中の場合は途中で止める
+    if !remove_pending(handler, guild_id, message_id) {
+        stop_playing(handler, guild_id, message_id);
+    }
+}
+
+/// まだ読み上げていないメッセージを生成中のリストと queue から取り除く
+///
+/// 取り除いた場合は true を返す
+pub(super) fn remove_pending(handler: &Handler, guild_id: GuildId, message_id: MessageId) -> bool {
+    let removed_pending = handler.pending_messages.with_write(|lock| {
+        let Some(pending) = lock.get_mut(&guild_id) else {
+            return false;
+        };
+
+        let before_len = pending.len();
+        pending.retain(|(id, _)| *id != message_id);
+
+        pending.len() != before_len
+    });
+
+    let removed_queue = handler.channel_queues.with_write(|lock| {
+        let Some(queue) = lock.get_mut(&guild_id) else {
+            return false;
+        };
+
+        let before_len = queue.len();
+        queue.retain(|item| item.is_playing || item.message_id != Some(message_id));
+
+        queue.len() != before_len
+    });
+
+    if removed_pending || removed_queue {
+        log::debug!("Removed from queue: {{ GuildID: {guild_id}, MessageID: {message_id} }}");
+    }
+
+    removed_pending || removed_queue
+}
+
+/// 読み上げ中のメッセージの再生を止める
+fn stop_playing(handler: &Handler, guild_id: GuildId, message_id: MessageId) {
+    handler.channel_queues.with_read(|lock| {
+        let Some(queue) = lock.get(&guild_id) else {
+            return;
+        };
+
+        let playing = queue
+            .iter()
+            .find(|item| item.is_playing && item.message_id == Some(message_id));
+
+        if let Some(item) = playing {
+            if let Some(track_handle) = &item.track_handle {
+                if let Err(err) = track_handle.stop() {
+                    log::error!("Failed to stop track: {err}");
+                }
+            }
+
+            // 再生時間分のスリープを打ち切って次に移らせる
+            item.skip_notify.notify_one();
+
+            log::debug!("Stopped playing: {{ GuildID: {guild_id}, MessageID: {message_id} }}");
+        }
+    });
+}
@@ -0,0 +1,52 @@
+use serenity::all::{Context, Message, MessageUpdateEvent};
+use sonorust_db::GuildData;
+
+use crate::{crate_extensions::rwlock::RwLockExt, errors::SonorustError, Handler};
+
+use super::{message::other_processing, message_delete::remove_pending};
+
+pub async fn message_update(
+    handler: &Handler,
+    ctx: &Context,
+    new: &Option<Message>,
+    event: &MessageUpdateEvent,
+) -> Result<(), SonorustError> {
+    // サーバー上でない場合何もしない
+    let Some(guild_id) = event.guild_id else {
+        return Ok(());
+    };
+
+    // 本文が変わっていない場合 (埋め込みの展開など) は何もしない
+    if event.content.is_none() {
+        return Ok(());
+    }
+
+    // 編集されたメッセージを読み直す設定でない場合何もしない
+    let guilddata = GuildData::from(guild_id).await?;
+    if !guilddata.options.is_reread_edited {
+        return Ok(());
+    }
+
+    // まだ読み上げていないメッセージのみ取り消して読み直す
+    if !remove_pending(handler, guild_id, event.id) {
+        return Ok(());
+    }
+
+    let msg = match new {
+        Some(msg) => msg.clone(),
+        None => event.channel_id.message(&ctx.http, event.id).await?,
+    };
+
+    // コマンドに編集された場合は読み上げない
+    let prefix = handler.setting_json.with_read(|lock| lock.prefix.clone());
+    if msg.content.starts_with(&prefix) {
+        return Ok(());
+    }
+
+    log::debug!(
+        "Reread edited message: {{ GuildID: {guild_id}, MessageID: {} }}",
+        event.id
+    );
+
+    other_processing(handler, ctx, &msg).await
+}
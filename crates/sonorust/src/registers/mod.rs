@@ -1,13 +1,15 @@
 mod component;
 mod message;
+mod message_delete;
+mod message_update;
 mod ready;
 mod slash_command;
 mod voice_state_update;
 
 pub use component::component;
 pub use message::message;
+pub use message_delete::message_delete;
+pub use message_update::message_update;
 pub use ready::ready;
 pub use slash_command::slash_command;
 pub use voice_state_update::voice_state_update;
-
-pub use message::TextReplace;
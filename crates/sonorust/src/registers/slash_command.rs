@@ -7,9 +7,11 @@ use serenity::all::{
     CreateInteractionResponseFollowup, CreateInteractionResponseMessage, EditMessage,
     ResolvedOption, ResolvedValue,
 };
+use sonorust_db::Permission;
 
 use crate::{
-    commands, Handler,
+    commands::{self, dict::format::DictFormat},
+    Handler,
     _langrustang_autogen::Lang,
     crate_extensions::{
         infer_api::InferApiExt, rwlock::RwLockExt, sonorust_setting::SettingJsonExt,
@@ -104,6 +106,7 @@ pub async fn slash_command(
                     interaction.guild_id,
                     interaction.channel_id,
                     interaction.user.id,
+                    None,
                     lang_t!("join.connected", lang),
                 )
                 .await?;
@@ -167,6 +170,23 @@ pub async fn slash_command(
             let content = commands::length(interaction.user.id, length, lang).await?;
             eq_uilibrium::create_response_msg!(interaction, &ctx.http, content = content).await?;
         }
+        "read_name" => {
+            debug_log();
+
+            // スラッシュコマンドの引数を取得 (指定されていなければリセット)
+            let command_args = &interaction.data.options();
+            let read_name = match command_args.first() {
+                Some(ResolvedOption {
+                    value: ResolvedValue::String(s),
+                    ..
+                }) => Some(*s),
+
+                _ => None,
+            };
+
+            let content = commands::read_name(interaction.user.id, read_name, lang).await?;
+            eq_uilibrium::create_response_msg!(interaction, &ctx.http, content = content).await?;
+        }
         "wav" => {
             // Defer を送信
             let msg = CreateInteractionResponseMessage::new();
@@ -193,10 +213,143 @@ pub async fn slash_command(
 
             interaction.create_followup(&ctx.http, builder).await?;
         }
+        "preview" => {
+            debug_log();
+
+            // スラッシュコマンドの引数を取得
+            let command_args = &interaction.data.options();
+            let text = match command_args.first() {
+                Some(ResolvedOption {
+                    value: ResolvedValue::String(text),
+                    ..
+                }) => *text,
+
+                _ => "",
+            };
+
+            let embed = commands::preview(
+                handler,
+                ctx,
+                interaction.guild_id,
+                interaction.user.id,
+                text,
+                lang,
+            )
+            .await?;
+            eq_uilibrium::create_response_msg!(interaction, &ctx.http, embed = embed).await?;
+        }
         "dict" => {
             debug_log();
 
-            let (embed, components) = commands::dict(ctx, interaction.guild_id, lang).await?;
+            // サブコマンドとその引数を取得
+            let command_args = &interaction.data.options();
+            let (subcommand, sub_args) = match command_args.first() {
+                Some(ResolvedOption {
+                    name,
+                    value: ResolvedValue::SubCommand(args),
+                    ..
+                }) => (*name, args.as_slice()),
+
+                _ => ("menu", [].as_slice()),
+            };
+
+            match subcommand {
+                "search" => {
+                    let query = sub_args
+                        .iter()
+                        .find_map(|opt| match opt.value {
+                            ResolvedValue::String(s) => Some(s),
+                            _ => None,
+                        })
+                        .unwrap_or_default();
+
+                    let (embed, components) =
+                        commands::dict::dict_page(ctx, interaction.guild_id, lang, query, 0)
+                            .await?;
+                    eq_uilibrium::create_response_msg!(
+                        interaction,
+                        &ctx.http,
+                        embed = embed,
+                        components = components,
+                    )
+                    .await?;
+                }
+                "history" => {
+                    let (embed, components) =
+                        commands::dict::dict_history(ctx, interaction.guild_id, lang).await?;
+                    eq_uilibrium::create_response_msg!(
+                        interaction,
+                        &ctx.http,
+                        embed = embed,
+                        components = components,
+                    )
+                    .await?;
+                }
+                "export" => {
+                    let format = sub_args
+                        .iter()
+                        .find_map(|opt| match opt.value {
+                            ResolvedValue::String(s) => DictFormat::from_name(s),
+                            _ => None,
+                        })
+                        .unwrap_or(DictFormat::Json);
+
+                    let (attachment, content) =
+                        commands::dict::dict_export(interaction.guild_id, format, lang).await?;
+
+                    let message = CreateInteractionResponseMessage::new()
+                        .content(content)
+                        .add_file(attachment);
+                    let builder = CreateInteractionResponse::Message(message);
+                    interaction.create_response(&ctx.http, builder).await?;
+                }
+                "import" => {
+                    let Some(attachment) = sub_args.iter().find_map(|opt| match opt.value {
+                        ResolvedValue::Attachment(attachment) => Some(attachment),
+                        _ => None,
+                    }) else {
+                        return Ok(());
+                    };
+
+                    let is_replace = sub_args
+                        .iter()
+                        .any(|opt| matches!(opt.value, ResolvedValue::String("replace")));
+
+                    // ファイルの取得に時間がかかることがあるので Defer を送信
+                    let msg = CreateInteractionResponseMessage::new();
+                    let builder = CreateInteractionResponse::Defer(msg);
+                    interaction.create_response(&ctx.http, builder).await?;
+
+                    let content = commands::dict::dict_import(
+                        ctx,
+                        interaction.guild_id,
+                        interaction.user.id,
+                        attachment,
+                        is_replace,
+                        lang,
+                    )
+                    .await?;
+
+                    let builder = CreateInteractionResponseFollowup::new().content(content);
+                    interaction.create_followup(&ctx.http, builder).await?;
+                }
+                _ => {
+                    let (embed, components) =
+                        commands::dict(ctx, interaction.guild_id, lang).await?;
+                    eq_uilibrium::create_response_msg!(
+                        interaction,
+                        &ctx.http,
+                        embed = embed,
+                        components = components,
+                    )
+                    .await?;
+                }
+            }
+        }
+        "globaldict" => {
+            debug_log();
+
+            let (embed, components) = commands::globaldict(ctx, interaction.user.id, lang).await?;
             eq_uilibrium::create_response_msg!(
                 interaction,
                 &ctx.http,
@@ -205,6 +358,19 @@ pub async fn slash_command(
             )
             .await?;
         }
+        "mydict" => {
+            debug_log();
+
+            let (embed, components) = commands::mydict(&interaction.user, lang).await?;
+            eq_uilibrium::create_response_msg!(
+                interaction,
+                &ctx.http,
+                embed = embed,
+                components = components,
+                ephemeral = true
+            )
+            .await?;
+        }
         "now" => {
             debug_log();
 
@@ -290,6 +456,79 @@ pub async fn slash_command(
             let builder = CreateInteractionResponse::Message(create_message);
             interaction.create_response(&ctx.http, builder).await?;
         }
+        "ignore" => {
+            debug_log();
+
+            let command_args = &interaction.data.options();
+            let target_user = command_args.first().map(|opt| match opt.value {
+                ResolvedValue::User(user, _) => user.id,
+                _ => unreachable!(),
+            });
+
+            let (embed, text) = commands::ignore(
+                ctx,
+                interaction.guild_id,
+                interaction.user.id,
+                lang,
+                target_user,
+            )
+            .await?;
+
+            let mut create_message = CreateInteractionResponseMessage::new();
+
+            if let Some(embed) = embed {
+                create_message = create_message.add_embed(embed);
+            }
+
+            if let Some(text) = text {
+                create_message = create_message.content(text);
+            }
+
+            let builder = CreateInteractionResponse::Message(create_message);
+            interaction.create_response(&ctx.http, builder).await?;
+        }
+        "permission" => {
+            debug_log();
+
+            let command_args = &interaction.data.options();
+
+            let permission = command_args.first().and_then(|opt| match opt.value {
+                ResolvedValue::String(s) => Permission::from_name(s),
+                _ => unreachable!(),
+            });
+            let role = command_args.get(1).map(|opt| match opt.value {
+                ResolvedValue::Role(role) => role.id,
+                _ => unreachable!(),
+            });
+
+            let (embed, text) = commands::permission(
+                ctx,
+                interaction.guild_id,
+                interaction.user.id,
+                lang,
+                permission.zip(role),
+            )
+            .await?;
+
+            let mut create_message = CreateInteractionResponseMessage::new();
+
+            if let Some(embed) = embed {
+                create_message = create_message.add_embed(embed);
+            }
+
+            if let Some(text) = text {
+                create_message = create_message.content(text);
+            }
+
+            let builder = CreateInteractionResponse::Message(create_message);
+            interaction.create_response(&ctx.http, builder).await?;
+        }
+        "read_me" => {
+            debug_log();
+
+            let content = commands::read_me(interaction.user.id, lang).await?;
+            eq_uilibrium::create_response_msg!(interaction, &ctx.http, content = content).await?;
+        }
         "read_add" => {
             debug_log();
 
@@ -339,14 +578,21 @@ pub fn registers(lang: Lang) -> Vec<CreateCommand> {
         commands::autojoin::create_command(lang),
         commands::clear::create_command(lang),
         commands::dict::create_command(lang),
+        commands::globaldict::create_command(lang),
+        commands::mydict::create_command(lang),
         commands::help::create_command(lang),
+        commands::ignore::create_command(lang),
         commands::join::create_command(lang),
         commands::leave::create_command(lang),
         commands::length::create_command(lang),
         commands::model::create_command(lang),
         commands::now::create_command(lang),
+        commands::permission::create_command(lang),
         commands::ping::create_command(),
+        commands::preview::create_command(lang),
         commands::read_add::create_command(lang),
+        commands::read_me::create_command(lang),
+        commands::read_name::create_command(lang),
         commands::read_remove::create_command(lang),
         commands::reload::create_command(lang),
         commands::server::create_command(lang),
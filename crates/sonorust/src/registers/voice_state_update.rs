@@ -1,6 +1,6 @@
 use std::collections::{HashMap, VecDeque};
 
-use langrustang::lang_t;
+use langrustang::{format_t, lang_t};
 use serenity::{
     all::{ChannelId, Context, GuildId, UserId, VoiceState},
     futures::{stream::FuturesUnordered, StreamExt},
@@ -9,7 +9,8 @@ use sonorust_db::GuildData;
 
 use crate::{
     crate_extensions::{
-        infer_api::InferApiExt, rwlock::RwLockExt, sonorust_setting::SettingJsonExt,
+        infer_api::InferApiExt, rwlock::RwLockExt, serenity::SerenityUserExt,
+        sonorust_setting::SettingJsonExt,
     },
     errors::SonorustError,
     Handler,
@@ -139,9 +140,16 @@ async fn auto_join(
     };
 
     // サーバーIDと読み上げるチャンネルIDのペアを登録
+    // オプションがオンならボイスチャンネルのチャットも読み上げる対象にする
+    let mut read_channels = join_set.clone();
+
+    if guilddata.options.is_read_vc_chat {
+        read_channels.insert(in_user_channel);
+    }
+
     handler
         .read_channels
-        .with_write(|lock| lock.insert(guild_id, join_set.clone()));
+        .with_write(|lock| lock.insert(guild_id, read_channels));
 
     // 読み上げ queue を初期化
     handler
@@ -183,6 +191,7 @@ async fn auto_join(
             Some(guild_id),
             *ch,
             user_id,
+            None,
             lang_t!("join.connected", lang),
         ));
     }
@@ -308,10 +317,7 @@ async fn entrance_exit_log_play(
         return Ok(());
     };
 
-    let user_name = user
-        .nick_in(&ctx.http, guild_id)
-        .await
-        .unwrap_or_else(|| user.global_name.unwrap_or_else(|| user.name));
+    let user_name = user.name_in_guild(&ctx.http, guild_id).await;
 
     // 読み上げているチャンネルを取得 取得できなかった場合リターン
     let log_channels = {
@@ -325,6 +331,7 @@ async fn entrance_exit_log_play(
 
     // サーバーデータの取得
     let guild_data = GuildData::from(guild_id).await?;
+    let lang = handler.setting_json.get_bot_lang();
 
     // 入退出ログと入退出音声通知の並列実行
     {
@@ -335,8 +342,8 @@ async fn entrance_exit_log_play(
             log_future = Some(async {
                 // メッセージを送信
                 let msg = match user_action {
-                    UserAction::Entrance => format!("> **{}** さんが参加しました。", user_name),
-                    UserAction::Exit => format!("> **{}** さんが退席しました。", user_name),
+                    UserAction::Entrance => format_t!("msg.entrance_log", lang, user_name),
+                    UserAction::Exit => format_t!("msg.exit_log", lang, user_name),
                 };
 
                 let mut tasks = FuturesUnordered::new();
@@ -356,14 +363,14 @@ async fn entrance_exit_log_play(
         if guild_data.options.is_entrance_exit_play {
             voice_future = Some(async {
                 let msg = match user_action {
-                    UserAction::Entrance => format!("{} さんが参加しました。", user_name),
-                    UserAction::Exit => format!("{} さんが退席しました。", user_name),
+                    UserAction::Entrance => format_t!("text.entrance", lang, user_name),
+                    UserAction::Exit => format_t!("text.exit", lang, user_name),
                 };
 
                 if let Some(channel) = log_channels.iter().next() {
                     handler
                         .infer_client
-                        .play_on_vc(handler, ctx, Some(guild_id), *channel, user_id, &msg)
+                        .play_on_vc(handler, ctx, Some(guild_id), *channel, user_id, None, &msg)
                         .await?;
                 }
 
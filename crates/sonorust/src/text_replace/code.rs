@@ -0,0 +1,142 @@
+use std::sync::LazyLock;
+
+use langrustang::{format_t, lang_t};
+use regex::{Captures, Regex};
+
+use crate::_langrustang_autogen::Lang;
+
+// (言語の指定, 読み上げる名前)
+// ここにない指定はそのまま読む
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("rust", "Rust"),
+    ("py", "Python"),
+    ("python", "Python"),
+    ("js", "JavaScript"),
+    ("javascript", "JavaScript"),
+    ("ts", "TypeScript"),
+    ("typescript", "TypeScript"),
+    ("c", "C"),
+    ("cpp", "C++"),
+    ("c++", "C++"),
+    ("cs", "C#"),
+    ("csharp", "C#"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kotlin", "Kotlin"),
+    ("rb", "Ruby"),
+    ("ruby", "Ruby"),
+    ("sh", "Shell"),
+    ("bash", "Bash"),
+    ("ps1", "PowerShell"),
+    ("powershell", "PowerShell"),
+    ("html", "HTML"),
+    ("css", "CSS"),
+    ("json", "JSON"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("toml", "TOML"),
+    ("sql", "SQL"),
+    ("md", "Markdown"),
+    ("markdown", "Markdown"),
+];
+
+/// ```言語\n...``` (閉じていない場合は最後までをコードブロックとする)
+static CODEBLOCK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)```(?:(?<lang>[\w+#\-\.]+)[ \t]*\n)?.*?(?:```|\z)").expect("Regex Failed")
+});
+
+/// `インラインコード`
+static INLINE_CODE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"`.*?`").expect("Regex Failed"));
+
+/// コードブロックを「Rust のコード」のように、インラインコードを「コード」に置換する
+/// (コードブロックの外の文章はそのまま残す)
+pub(super) fn replace_code(text: &str, lang: Lang) -> String {
+    let text = CODEBLOCK_RE.replace_all(text, |caps: &Captures| {
+        let language = caps.name("lang").map(|m| {
+            let tag = m.as_str();
+
+            LANGUAGE_NAMES
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(tag))
+                .map(|(_, name)| *name)
+                .unwrap_or(tag)
+        });
+
+        // 前後の文章とつながらないように区切る
+        let code = match language {
+            Some(language) => format_t!("text.codeblock_lang", lang, language),
+            None => lang_t!("text.codeblock", lang).to_string(),
+        };
+
+        format!(" {code} ")
+    });
+
+    let text = INLINE_CODE_RE.replace_all(&text, lang_t!("text.code", lang));
+
+    text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codeblock() {
+        let codeblock = lang_t!("text.codeblock", Lang::Ja);
+
+        assert_eq!(
+            replace_code("```rust\nfn main() {}\n```", Lang::Ja),
+            format_t!("text.codeblock_lang", Lang::Ja, "Rust")
+        );
+        assert_eq!(
+            replace_code("```RS\nfn main() {}\n```", Lang::En),
+            format_t!("text.codeblock_lang", Lang::En, "Rust")
+        );
+        assert_eq!(
+            replace_code("```zig\nconst x = 1;\n```", Lang::Ja),
+            format_t!("text.codeblock_lang", Lang::Ja, "zig")
+        );
+        assert_eq!(replace_code("```\ncode\n```", Lang::Ja), codeblock);
+
+        // 1行のコードブロックは言語の指定がないものとして扱う
+        assert_eq!(replace_code("```let x = 1;```", Lang::Ja), codeblock);
+    }
+
+    #[test]
+    fn test_unclosed_codeblock() {
+        assert_eq!(
+            replace_code("これ\n```py\nprint(1)", Lang::Ja),
+            format!(
+                "これ\n {}",
+                format_t!("text.codeblock_lang", Lang::Ja, "Python")
+            )
+        );
+    }
+
+    #[test]
+    fn test_surrounding_text() {
+        let code = format_t!("text.codeblock_lang", Lang::Ja, "JavaScript");
+
+        assert_eq!(
+            replace_code("これ```js\na()\n```どう?", Lang::Ja),
+            format!("これ {code} どう?")
+        );
+    }
+
+    #[test]
+    fn test_inline_code() {
+        let code = lang_t!("text.code", Lang::Ja);
+
+        assert_eq!(
+            replace_code("`cargo build` を実行", Lang::Ja),
+            format!("{code} を実行")
+        );
+        assert_eq!(
+            replace_code("`a` と `b`", Lang::Ja),
+            format!("{code} と {code}")
+        );
+    }
+}
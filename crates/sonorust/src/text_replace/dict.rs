@@ -0,0 +1,282 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use aho_corasick::{AhoCorasick, MatchKind};
+use regex::{Regex, RegexBuilder};
+use serenity::all::{GuildId, UserId};
+use sonorust_db::{DictEntry, GlobalDict, GuildData, MatchMode, UserData};
+
+use crate::crate_extensions::rwlock::RwLockExt;
+
+/// 辞書をもとに文字列を置換する
+/// 一致する範囲が重なった場合は優先度が高いもの、同じ優先度なら左にあるもの、
+/// 同じ位置なら一番長いものを使う (置換後の文字列がさらに置換されることはない)
+#[derive(Debug)]
+pub struct DictReplacer {
+    /// Literal と Word の単語をまとめたもの
+    automaton: Option<AhoCorasick>,
+    words: Vec<WordEntry>,
+    regexes: Vec<RegexEntry>,
+}
+
+#[derive(Debug)]
+struct WordEntry {
+    after_text: String,
+    is_word: bool,
+    priority: i64,
+    order: usize,
+}
+
+#[derive(Debug)]
+struct RegexEntry {
+    regex: Regex,
+    after_text: String,
+    priority: i64,
+    order: usize,
+}
+
+#[derive(Debug)]
+struct Candidate<'a> {
+    start: usize,
+    end: usize,
+    priority: i64,
+    order: usize,
+    after_text: Cow<'a, str>,
+}
+
+impl DictReplacer {
+    pub fn new<'a, I>(dict: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a String, &'a DictEntry)>,
+    {
+        // 空文字は置換対象にしない、結果が変わらないように単語順に並べる
+        let mut dict: Vec<_> = dict
+            .into_iter()
+            .filter(|(before, _)| !before.is_empty())
+            .collect();
+        dict.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut before_texts = vec![];
+        let mut words = vec![];
+        let mut regexes = vec![];
+
+        for (order, (before, entry)) in dict.into_iter().enumerate() {
+            match entry.mode {
+                MatchMode::Literal | MatchMode::Word => {
+                    before_texts.push(before.as_str());
+                    words.push(WordEntry {
+                        after_text: entry.after_text.clone(),
+                        is_word: entry.mode == MatchMode::Word,
+                        priority: entry.priority,
+                        order,
+                    });
+                }
+                MatchMode::Regex => {
+                    let Ok(regex) = build_regex(before)
+                        .inspect_err(|err| log::error!("Invalid dictionary regex: {err}"))
+                    else {
+                        continue;
+                    };
+
+                    regexes.push(RegexEntry {
+                        regex,
+                        after_text: entry.after_text.clone(),
+                        priority: entry.priority,
+                        order,
+                    });
+                }
+            }
+        }
+
+        let automaton = match before_texts.is_empty() {
+            true => None,
+            false => AhoCorasick::builder()
+                .match_kind(MatchKind::Standard)
+                .build(&before_texts)
+                .inspect_err(|err| log::error!("Failed to build dictionary: {err}"))
+                .ok(),
+        };
+
+        Self {
+            automaton,
+            words,
+            regexes,
+        }
+    }
+
+    pub fn replace(&self, text: &str) -> String {
+        let mut candidates = vec![];
+
+        if let Some(automaton) = &self.automaton {
+            for mat in automaton.find_overlapping_iter(text) {
+                let word = &self.words[mat.pattern().as_usize()];
+
+                if word.is_word && !is_word_match(text, mat.start(), mat.end()) {
+                    continue;
+                }
+
+                candidates.push(Candidate {
+                    start: mat.start(),
+                    end: mat.end(),
+                    priority: word.priority,
+                    order: word.order,
+                    after_text: Cow::Borrowed(&word.after_text),
+                });
+            }
+        }
+
+        for entry in &self.regexes {
+            for captures in entry.regex.captures_iter(text) {
+                let Some(mat) = captures.get(0).filter(|mat| !mat.is_empty()) else {
+                    continue;
+                };
+
+                let mut after_text = String::new();
+                captures.expand(&entry.after_text, &mut after_text);
+
+                candidates.push(Candidate {
+                    start: mat.start(),
+                    end: mat.end(),
+                    priority: entry.priority,
+                    order: entry.order,
+                    after_text: Cow::Owned(after_text),
+                });
+            }
+        }
+
+        if candidates.is_empty() {
+            return text.to_string();
+        }
+
+        candidates.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(a.start.cmp(&b.start))
+                .then(b.end.cmp(&a.end))
+                .then(a.order.cmp(&b.order))
+        });
+
+        // BTreeMap<start, (end, 置換後の文字列)>
+        let mut accepted: BTreeMap<usize, (usize, Cow<str>)> = BTreeMap::new();
+
+        for candidate in candidates {
+            let overlaps_prev = accepted
+                .range(..=candidate.start)
+                .next_back()
+                .is_some_and(|(_, (end, _))| *end > candidate.start);
+            let overlaps_next = accepted
+                .range(candidate.start..)
+                .next()
+                .is_some_and(|(start, _)| *start < candidate.end);
+
+            if !overlaps_prev && !overlaps_next {
+                accepted.insert(candidate.start, (candidate.end, candidate.after_text));
+            }
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut last_end = 0;
+
+        for (start, (end, after_text)) in accepted {
+            result.push_str(&text[last_end..start]);
+            result.push_str(&after_text);
+            last_end = end;
+        }
+        result.push_str(&text[last_end..]);
+
+        result
+    }
+}
+
+/// 辞書の正規表現を作る (大きすぎるものはエラーにする)
+pub fn build_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).size_limit(1 << 20).build()
+}
+
+/// 一致した範囲の前後が単語の区切りになっているか
+fn is_word_match(text: &str, start: usize, end: usize) -> bool {
+    let is_word_char = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+    let matched = &text[start..end];
+
+    let start_ok =
+        is_word_char(text[..start].chars().next_back()) != is_word_char(matched.chars().next());
+    let end_ok =
+        is_word_char(matched.chars().next_back()) != is_word_char(text[end..].chars().next());
+
+    start_ok && end_ok
+}
+
+/// HashMap<GuildId or UserId, (辞書のバージョン, DictReplacer)> (全体辞書はキーなし)
+type ReplacerCache<K> = HashMap<K, (u64, Arc<DictReplacer>)>;
+
+/// サーバー辞書ごとの DictReplacer (辞書が更新されるまで使いまわす)
+static GUILD_DICT_REPLACERS: LazyLock<RwLock<ReplacerCache<GuildId>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// 個人辞書ごとの DictReplacer
+static USER_DICT_REPLACERS: LazyLock<RwLock<ReplacerCache<UserId>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// 全体辞書の DictReplacer
+static GLOBAL_DICT_REPLACER: LazyLock<RwLock<ReplacerCache<()>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+pub fn global_dict_replacer(global_dict: &GlobalDict) -> Arc<DictReplacer> {
+    cached_replacer(
+        &GLOBAL_DICT_REPLACER,
+        (),
+        global_dict.dict_version,
+        &global_dict.dict,
+    )
+}
+
+pub fn guild_dict_replacer(guilddata: &GuildData) -> Arc<DictReplacer> {
+    cached_replacer(
+        &GUILD_DICT_REPLACERS,
+        guilddata.guild_id,
+        guilddata.dict_version,
+        &guilddata.dict,
+    )
+}
+
+pub fn user_dict_replacer(userdata: &UserData) -> Arc<DictReplacer> {
+    cached_replacer(
+        &USER_DICT_REPLACERS,
+        userdata.user_id,
+        userdata.dict_version,
+        &userdata.dict,
+    )
+}
+
+/// キャッシュのバージョンが同じならそれを使い、違えば作り直す
+fn cached_replacer<K>(
+    cache: &RwLock<ReplacerCache<K>>,
+    key: K,
+    version: u64,
+    dict: &HashMap<String, DictEntry>,
+) -> Arc<DictReplacer>
+where
+    K: Eq + Hash,
+{
+    let cached = cache.with_read(|lock| {
+        lock.get(&key)
+            .filter(|(cached_version, _)| *cached_version == version)
+            .map(|(_, replacer)| replacer.clone())
+    });
+
+    if let Some(replacer) = cached {
+        return replacer;
+    }
+
+    let replacer = Arc::new(DictReplacer::new(dict));
+
+    cache.with_write(|lock| {
+        lock.insert(key, (version, replacer.clone()));
+    });
+
+    replacer
+}
@@ -0,0 +1,235 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use crate::_langrustang_autogen::Lang;
+
+// (絵文字, 日本語読み, 英語読み)
+// 絵文字は異体字セレクタ (U+FE0F) と肌の色の修飾子を除いた形で登録する
+const EMOJI_NAMES: &[(&str, &str, &str)] = &[
+    // 顔
+    ("😀", "にっこり", "grinning face"),
+    ("😃", "にこにこ", "grinning face with big eyes"),
+    ("😄", "笑顔", "grinning face with smiling eyes"),
+    ("😁", "にかっ", "beaming face"),
+    ("😆", "大笑い", "grinning squinting face"),
+    ("😅", "苦笑い", "grinning face with sweat"),
+    ("🤣", "爆笑", "rolling on the floor laughing"),
+    ("😂", "嬉し泣き", "face with tears of joy"),
+    ("🙂", "微笑み", "slightly smiling face"),
+    ("🙃", "逆さまの顔", "upside down face"),
+    ("😉", "ウインク", "winking face"),
+    ("😊", "照れ笑い", "smiling face"),
+    ("😇", "天使の笑顔", "smiling face with halo"),
+    ("🥰", "ハートの笑顔", "smiling face with hearts"),
+    ("😍", "目がハート", "heart eyes"),
+    ("🤩", "目が星", "star struck"),
+    ("😘", "投げキッス", "face blowing a kiss"),
+    ("😋", "おいしい", "yum"),
+    ("😛", "べー", "face with tongue"),
+    ("😜", "ふざけた顔", "winking face with tongue"),
+    ("🤪", "おどけた顔", "zany face"),
+    ("🤔", "考え中", "thinking face"),
+    ("🤗", "ハグ", "hugging face"),
+    ("🤭", "くすくす", "face with hand over mouth"),
+    ("🤫", "しー", "shushing face"),
+    ("🤐", "お口チャック", "zipper mouth face"),
+    ("😐", "真顔", "neutral face"),
+    ("😑", "無表情", "expressionless face"),
+    ("😶", "無言", "face without mouth"),
+    ("😏", "にやり", "smirking face"),
+    ("😒", "不満顔", "unamused face"),
+    ("🙄", "呆れ顔", "face with rolling eyes"),
+    ("😬", "しかめっ面", "grimacing face"),
+    ("😌", "ほっ", "relieved face"),
+    ("😔", "しょんぼり", "pensive face"),
+    ("😪", "眠い", "sleepy face"),
+    ("😴", "すやすや", "sleeping face"),
+    ("😷", "マスク", "face with medical mask"),
+    ("🤒", "熱", "face with thermometer"),
+    ("🤢", "吐き気", "nauseated face"),
+    ("🤮", "おえー", "face vomiting"),
+    ("🥵", "暑い", "hot face"),
+    ("🥶", "寒い", "cold face"),
+    ("🥴", "ふらふら", "woozy face"),
+    ("😵", "目が回る", "dizzy face"),
+    ("🤯", "頭爆発", "exploding head"),
+    ("🥳", "お祝い", "partying face"),
+    ("😎", "サングラス", "smiling face with sunglasses"),
+    ("🤓", "オタク", "nerd face"),
+    ("🧐", "モノクル", "face with monocle"),
+    ("😕", "困惑", "confused face"),
+    ("😟", "心配", "worried face"),
+    ("🙁", "しかめ顔", "slightly frowning face"),
+    ("😮", "おお", "face with open mouth"),
+    ("😯", "びっくり", "hushed face"),
+    ("😲", "驚き", "astonished face"),
+    ("😳", "赤面", "flushed face"),
+    ("🥺", "お願い", "pleading face"),
+    ("😦", "がーん", "frowning face with open mouth"),
+    ("😨", "恐怖", "fearful face"),
+    ("😰", "冷や汗", "anxious face with sweat"),
+    ("😥", "がっかり", "sad but relieved face"),
+    ("😢", "泣き顔", "crying face"),
+    ("😭", "号泣", "loudly crying face"),
+    ("😱", "絶叫", "face screaming in fear"),
+    ("😖", "困り顔", "confounded face"),
+    ("😣", "我慢", "persevering face"),
+    ("😞", "落胆", "disappointed face"),
+    ("😓", "汗", "downcast face with sweat"),
+    ("😩", "うんざり", "weary face"),
+    ("😫", "疲れた", "tired face"),
+    ("🥱", "あくび", "yawning face"),
+    ("😤", "ふんっ", "face with steam from nose"),
+    ("😡", "激怒", "pouting face"),
+    ("😠", "怒り", "angry face"),
+    ("🤬", "罵り", "face with symbols on mouth"),
+    ("😈", "悪い笑顔", "smiling face with horns"),
+    ("💀", "ドクロ", "skull"),
+    ("💩", "うんち", "pile of poo"),
+    ("🤡", "ピエロ", "clown face"),
+    ("👻", "おばけ", "ghost"),
+    ("👽", "宇宙人", "alien"),
+    ("🤖", "ロボット", "robot"),
+    // 手
+    ("👍", "いいね", "thumbs up"),
+    ("👎", "よくないね", "thumbs down"),
+    ("👌", "オーケー", "ok hand"),
+    ("✌", "ピース", "victory hand"),
+    ("🤞", "幸運を祈る", "crossed fingers"),
+    ("🤟", "ラブユー", "love you gesture"),
+    ("🤘", "ロック", "sign of the horns"),
+    ("👋", "バイバイ", "waving hand"),
+    ("👏", "拍手", "clapping hands"),
+    ("🙌", "ばんざい", "raising hands"),
+    ("🙏", "お願いします", "folded hands"),
+    ("🤝", "握手", "handshake"),
+    ("💪", "力こぶ", "flexed biceps"),
+    ("👉", "右指差し", "pointing right"),
+    ("👈", "左指差し", "pointing left"),
+    ("👆", "上指差し", "pointing up"),
+    ("👇", "下指差し", "pointing down"),
+    ("✋", "手のひら", "raised hand"),
+    ("👊", "パンチ", "oncoming fist"),
+    ("👀", "目", "eyes"),
+    ("🙇", "お辞儀", "person bowing"),
+    ("🤷", "さあね", "person shrugging"),
+    ("🤦", "やれやれ", "person facepalming"),
+    ("🙆", "オーケー", "person gesturing ok"),
+    ("🙅", "ダメ", "person gesturing no"),
+    // ハート・記号
+    ("❤", "ハート", "red heart"),
+    ("🧡", "オレンジのハート", "orange heart"),
+    ("💛", "黄色のハート", "yellow heart"),
+    ("💚", "緑のハート", "green heart"),
+    ("💙", "青いハート", "blue heart"),
+    ("💜", "紫のハート", "purple heart"),
+    ("🖤", "黒いハート", "black heart"),
+    ("🤍", "白いハート", "white heart"),
+    ("💔", "失恋", "broken heart"),
+    ("💕", "ハート", "two hearts"),
+    ("💖", "キラキラハート", "sparkling heart"),
+    ("❤\u{200D}🔥", "燃えるハート", "heart on fire"),
+    ("💯", "百点", "hundred points"),
+    ("💢", "怒りマーク", "anger symbol"),
+    ("💥", "どかーん", "collision"),
+    ("💦", "汗", "sweat droplets"),
+    ("💤", "ぐーぐー", "zzz"),
+    ("💬", "吹き出し", "speech balloon"),
+    ("💡", "ひらめき", "light bulb"),
+    ("✨", "キラキラ", "sparkles"),
+    ("⭐", "星", "star"),
+    ("🌟", "輝く星", "glowing star"),
+    ("🔥", "炎", "fire"),
+    ("⚡", "稲妻", "high voltage"),
+    ("🎉", "クラッカー", "party popper"),
+    ("🎊", "くす玉", "confetti ball"),
+    ("🎁", "プレゼント", "wrapped gift"),
+    ("🎂", "バースデーケーキ", "birthday cake"),
+    ("✅", "チェック", "check mark"),
+    ("✔", "チェック", "check mark"),
+    ("❌", "バツ", "cross mark"),
+    ("⭕", "丸", "hollow red circle"),
+    ("❗", "びっくりマーク", "exclamation mark"),
+    ("❓", "はてな", "question mark"),
+    ("‼", "びっくりマーク", "double exclamation mark"),
+    ("⁉", "びっくりはてな", "exclamation question mark"),
+    ("⚠", "注意", "warning"),
+    ("🚫", "禁止", "prohibited"),
+    ("🆗", "オーケー", "ok button"),
+    ("🆕", "ニュー", "new button"),
+    ("🆙", "アップ", "up button"),
+    ("1\u{20E3}", "いち", "one"),
+    ("2\u{20E3}", "に", "two"),
+    ("3\u{20E3}", "さん", "three"),
+    // 動物・自然
+    ("🐶", "いぬ", "dog"),
+    ("🐱", "ねこ", "cat"),
+    ("🐭", "ねずみ", "mouse"),
+    ("🐰", "うさぎ", "rabbit"),
+    ("🦊", "きつね", "fox"),
+    ("🐻", "くま", "bear"),
+    ("🐼", "パンダ", "panda"),
+    ("🐸", "かえる", "frog"),
+    ("🐧", "ペンギン", "penguin"),
+    ("🐟", "さかな", "fish"),
+    ("🌸", "桜", "cherry blossom"),
+    ("🌈", "虹", "rainbow"),
+    ("☀", "晴れ", "sun"),
+    ("☁", "くもり", "cloud"),
+    ("☔", "雨", "umbrella with rain drops"),
+    ("⛄", "雪だるま", "snowman"),
+    ("🌙", "三日月", "crescent moon"),
+    // 食べ物・その他
+    ("🍣", "寿司", "sushi"),
+    ("🍜", "ラーメン", "ramen"),
+    ("🍙", "おにぎり", "rice ball"),
+    ("🍺", "ビール", "beer"),
+    ("🍻", "乾杯", "clinking beer mugs"),
+    ("☕", "コーヒー", "hot beverage"),
+    ("🍰", "ケーキ", "shortcake"),
+    ("🎮", "ゲーム", "video game"),
+    ("🎵", "音符", "musical note"),
+    ("🎶", "音符", "musical notes"),
+    ("🎤", "マイク", "microphone"),
+    ("📢", "拡声器", "loudspeaker"),
+    ("📝", "メモ", "memo"),
+    ("📌", "ピン", "pushpin"),
+    ("🔔", "ベル", "bell"),
+    ("💰", "お金", "money bag"),
+    ("🚀", "ロケット", "rocket"),
+    ("🏆", "トロフィー", "trophy"),
+    ("🇯🇵", "日本の国旗", "flag of Japan"),
+    ("🇺🇸", "アメリカの国旗", "flag of the United States"),
+];
+
+// 一度に照合する最大の文字数 (ZWJ で結合された絵文字用)
+const MAX_EMOJI_CHARS: usize = 4;
+
+static EMOJI_MAP: LazyLock<HashMap<&str, (&str, &str)>> = LazyLock::new(|| {
+    EMOJI_NAMES
+        .iter()
+        .map(|(emoji, ja, en)| (*emoji, (*ja, *en)))
+        .collect()
+});
+
+/// 先頭の絵文字の文字数と読みを返す (一番長く一致したものを使う)
+pub(super) fn match_emoji(chars: &[char], lang: Lang) -> Option<(usize, &'static str)> {
+    let max_len = MAX_EMOJI_CHARS.min(chars.len());
+
+    (1..=max_len).rev().find_map(|len| {
+        let key: String = chars[..len].iter().collect();
+
+        EMOJI_MAP.get(key.as_str()).map(|(ja, en)| {
+            let name = match lang {
+                Lang::Ja => *ja,
+                Lang::En => *en,
+            };
+
+            (len, name)
+        })
+    })
+}
+
+/// 異体字セレクタと肌の色の修飾子
+pub(super) fn is_emoji_modifier(c: char) -> bool {
+    matches!(c, '\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}')
+}
@@ -0,0 +1,200 @@
+use std::{ops::Range, sync::LazyLock};
+
+use langrustang::lang_t;
+use regex::{Captures, Regex};
+
+use super::replace_with;
+use crate::_langrustang_autogen::Lang;
+
+// \* のようにエスケープされた記号を一時的に置き換える私用領域
+// (0x80 文字ずつに区切り、入力に含まれていない区画を使う)
+const ESCAPE_AREA: Range<u32> = 0xE000..0xF900;
+const ESCAPE_BLOCK: u32 = 0x80;
+
+// エスケープできる記号
+const ESCAPABLE: &str = r"\*_~|`>#-[]()";
+
+/// 行頭の見出し (#, ##, ###, -#)、引用 (>, >>>)、箇条書き (-, *)
+static LINE_PREFIX_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:>>> |> )?[ \t]*(?:#{1,3} |-# |[-*] )?").expect("Regex Failed")
+});
+
+/// ||ネタバレ||
+static SPOILER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)\|\|(?<text>.+?)\|\|").expect("Regex Failed"));
+
+/// [表示する文字](URL)
+static MASKED_LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[(?<text>[^\[\]\n]+)\]\(<?https?://[^\s()<>]+>?\)").expect("Regex Failed")
+});
+
+/// ***太字斜体***、**太字**、__下線__、~~取り消し線~~、*斜体*
+static EMPHASIS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\*\*\*(?<bold_italic>.+?)\*\*\*|\*\*(?<bold>.+?)\*\*|__(?<underline>.+?)__|~~(?<strike>.+?)~~|\*(?<italic>[^\s*](?:[^*]*[^\s*])?)\*",
+    )
+    .expect("Regex Failed")
+});
+
+/// _斜体_ (単語の途中の _ は斜体にならない)
+static UNDERSCORE_ITALIC_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"_(?<text>[^\s_](?:[^_]*[^\s_])?)_").expect("Regex Failed"));
+
+/// Discord のマークダウンを取り除いて読み上げる文字だけにする
+/// ネタバレは is_skip_spoiler が true なら読まず、false なら「ネタバレ」と読む
+pub(super) fn strip_markdown(text: &str, lang: Lang, is_skip_spoiler: bool) -> String {
+    let escape_base = find_escape_base(text);
+    let text = match escape_base {
+        Some(base) => escape(text, base),
+        None => text.to_string(),
+    };
+    let text = LINE_PREFIX_RE.replace_all(&text, "");
+
+    let spoiler = match is_skip_spoiler {
+        true => "",
+        false => lang_t!("text.spoiler", lang),
+    };
+    let text = SPOILER_RE.replace_all(&text, spoiler);
+    let text = MASKED_LINK_RE.replace_all(&text, "$text");
+
+    // **~~太字の取り消し線~~** のように重なっている場合があるので置換できなくなるまで繰り返す
+    let mut text = text.to_string();
+
+    loop {
+        let replaced = EMPHASIS_RE.replace_all(&text, |caps: &Captures| {
+            ["bold_italic", "bold", "underline", "strike", "italic"]
+                .into_iter()
+                .find_map(|name| caps.name(name))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default()
+        });
+
+        if replaced == text {
+            break;
+        }
+        text = replaced.to_string();
+    }
+
+    let text = replace_with(&text, &UNDERSCORE_ITALIC_RE, |caps, before, after| {
+        let is_in_word = before.is_some_and(|c| c.is_alphanumeric())
+            || after.is_some_and(|c| c.is_alphanumeric());
+
+        (!is_in_word).then(|| caps["text"].to_string())
+    });
+
+    match escape_base {
+        Some(base) => unescape(&text, base),
+        None => text,
+    }
+}
+
+/// 入力に含まれていない私用領域の区画の先頭を探す
+/// (ユーザーが入力した私用領域の文字を記号に戻さないように)
+fn find_escape_base(text: &str) -> Option<u32> {
+    ESCAPE_AREA.step_by(ESCAPE_BLOCK as usize).find(|base| {
+        !text
+            .chars()
+            .any(|c| (*base..*base + ESCAPE_BLOCK).contains(&(c as u32)))
+    })
+}
+
+/// \* などを私用領域の文字に置き換えて、マークダウンとして扱われないようにする
+fn escape(text: &str, base: u32) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match chars.next_if(|next| c == '\\' && ESCAPABLE.contains(*next)) {
+            Some(escaped) => result.push(char::from_u32(base + escaped as u32).unwrap_or(escaped)),
+            None => result.push(c),
+        }
+    }
+
+    result
+}
+
+fn unescape(text: &str, base: u32) -> String {
+    text.chars()
+        .map(|c| {
+            (c as u32)
+                .checked_sub(base)
+                .filter(|code| *code < ESCAPE_BLOCK)
+                .and_then(char::from_u32)
+                .unwrap_or(c)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(text: &str) -> String {
+        strip_markdown(text, Lang::Ja, false)
+    }
+
+    #[test]
+    fn test_line_prefix() {
+        assert_eq!(strip("# 見出し"), "見出し");
+        assert_eq!(strip("### 小見出し"), "小見出し");
+        assert_eq!(strip("-# 小さい文字"), "小さい文字");
+        assert_eq!(strip("> 引用"), "引用");
+        assert_eq!(strip(">>> 複数行の引用"), "複数行の引用");
+        assert_eq!(strip("- 一つ目\n* 二つ目"), "一つ目\n二つ目");
+
+        // 行頭以外の # や > は残す
+        assert_eq!(strip("C# > Java"), "C# > Java");
+    }
+
+    #[test]
+    fn test_spoiler() {
+        let spoiler = lang_t!("text.spoiler", Lang::Ja);
+
+        assert_eq!(
+            strip_markdown("||犯人は||です", Lang::Ja, false),
+            format!("{spoiler}です")
+        );
+        assert_eq!(strip_markdown("||犯人は||です", Lang::Ja, true), "です");
+    }
+
+    #[test]
+    fn test_emphasis() {
+        assert_eq!(strip("**太字**と*斜体*"), "太字と斜体");
+        assert_eq!(strip("***太字斜体***"), "太字斜体");
+        assert_eq!(strip("__下線__と~~取り消し線~~"), "下線と取り消し線");
+        assert_eq!(strip("**~~重なり~~**"), "重なり");
+        assert_eq!(strip("_斜体_"), "斜体");
+
+        // 単語の途中の _ は斜体にならない
+        assert_eq!(strip("snake_case_name"), "snake_case_name");
+    }
+
+    #[test]
+    fn test_masked_link() {
+        assert_eq!(strip("[公式サイト](https://example.com)"), "公式サイト");
+        assert_eq!(strip("[公式サイト](<https://example.com>)"), "公式サイト");
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(strip(r"\*太字ではない\*"), "*太字ではない*");
+        assert_eq!(strip(r"\||ネタバレではない\||"), "||ネタバレではない||");
+
+        // 入力された私用領域の文字は記号に戻さない
+        assert_eq!(strip("\u{E02A}**太字**"), "\u{E02A}太字");
+        assert_eq!(
+            strip("\u{E02A}\\*太字ではない\\*"),
+            "\u{E02A}*太字ではない*"
+        );
+    }
+
+    #[test]
+    fn test_unbalanced() {
+        // 閉じていない記号はそのまま残す
+        assert_eq!(strip("**閉じていない"), "**閉じていない");
+        assert_eq!(strip("||閉じていない"), "||閉じていない");
+        assert_eq!(strip("~~閉じていない"), "~~閉じていない");
+        assert_eq!(strip("2 * 3 = 6"), "2 * 3 = 6");
+        assert_eq!(strip("a * b * c"), "a * b * c");
+    }
+}
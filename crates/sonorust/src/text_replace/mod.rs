@@ -0,0 +1,362 @@
+mod code;
+pub mod dict;
+mod emoji;
+mod markdown;
+mod number;
+mod pipeline;
+mod slang;
+mod url;
+
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Local, Utc};
+use engtokana::{ConvertOptions, EngToKana};
+use langrustang::{format_t, lang_t};
+use regex::{Captures, Regex};
+use serenity::all::{Cache, ChannelId, GuildId, RoleId, UserId};
+use sonorust_db::{GlobalDict, GuildData, UserData};
+
+use crate::_langrustang_autogen::Lang;
+
+pub use pipeline::PipelineContext;
+
+#[derive(Debug, Clone)]
+pub struct TextReplace {
+    text: String,
+}
+
+impl TextReplace {
+    pub fn new<S>(text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { text: text.into() }
+    }
+
+    pub fn as_string(self) -> String {
+        self.text
+    }
+
+    /// コードブロックを「Rust のコード」のように、インラインコードを「コード」と読む
+    pub fn remove_codeblock(&mut self, lang: Lang) {
+        self.text = code::replace_code(&self.text, lang);
+    }
+
+    /// 見出しや太字などのマークダウンを取り除く
+    /// (ネタバレは is_skip_spoiler が true なら読まない)
+    pub fn strip_markdown(&mut self, lang: Lang, is_skip_spoiler: bool) {
+        self.text = markdown::strip_markdown(&self.text, lang, is_skip_spoiler);
+    }
+
+    /// URL を置換する (is_read_domain が true ならドメイン名で読む)
+    pub fn remove_url(&mut self, lang: Lang, is_read_domain: bool) {
+        self.text = url::replace_url(&self.text, lang, is_read_domain);
+    }
+
+    /// チャンネルやメンション、カスタム絵文字などの置換
+    pub fn remove_discord_obj(&mut self) {
+        let re = Regex::new(r"<.*?>").unwrap();
+        self.text = re.replace_all(&self.text, "").to_string()
+    }
+
+    /// メンション、チャンネル、ロール、カスタム絵文字、タイムスタンプを読める形に置換する
+    /// (キャッシュから名前を取得できなかったものは削除する)
+    pub fn replace_discord_obj(&mut self, cache: &Cache, guild_id: GuildId, lang: Lang) {
+        static RE: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(
+                r"<(?:@!?(?<user>\d+)|@&(?<role>\d+)|#(?<channel>\d+)|a?:(?<emoji>\w+):\d+|/(?<command>[^:<>]+):\d+|t:(?<timestamp>-?\d+)(?::(?<style>[tTdDfFR]))?)>",
+            )
+            .expect("Regex Failed")
+        });
+
+        let replaced = RE.replace_all(&self.text, |caps: &Captures| {
+            let get_id = |name| {
+                caps.name(name)
+                    .and_then(|m| m.as_str().parse::<u64>().ok())
+                    .filter(|id| *id != 0)
+            };
+
+            let replaced = if let Some(id) = get_id("user") {
+                user_display_name(cache, guild_id, UserId::new(id))
+            } else if let Some(id) = get_id("role") {
+                cache
+                    .guild(guild_id)
+                    .and_then(|guild| guild.roles.get(&RoleId::new(id)).map(|r| r.name.clone()))
+            } else if let Some(id) = get_id("channel") {
+                channel_name(cache, guild_id, ChannelId::new(id))
+            } else if let Some(emoji) = caps.name("emoji") {
+                Some(emoji.as_str().replace("_", " "))
+            } else if let Some(command) = caps.name("command") {
+                Some(command.as_str().to_string())
+            } else if let Some(timestamp) = caps.name("timestamp") {
+                let style = caps.name("style").map(|m| m.as_str());
+
+                timestamp
+                    .as_str()
+                    .parse()
+                    .ok()
+                    .and_then(|timestamp| timestamp_to_text(timestamp, style, lang))
+            } else {
+                None
+            };
+
+            replaced.unwrap_or_default()
+        });
+
+        self.text = replaced.to_string();
+
+        // 上で置換できなかったものは削除する
+        self.remove_discord_obj();
+    }
+
+    /// 絵文字を読みに置換する
+    /// 同じ絵文字が repeat_limit 個より多く続いた場合、それ以降は読まない
+    pub fn replace_emoji(&mut self, lang: Lang, repeat_limit: u32) {
+        let chars: Vec<char> = self
+            .text
+            .chars()
+            .filter(|c| !emoji::is_emoji_modifier(*c))
+            .collect();
+
+        let mut result = String::with_capacity(self.text.len());
+        let mut last_emoji: Option<&[char]> = None;
+        let mut repeat_count = 0;
+
+        let mut idx = 0;
+        while idx < chars.len() {
+            let Some((len, name)) = emoji::match_emoji(&chars[idx..], lang) else {
+                // 空白を挟んだだけなら連続しているとみなす
+                if !chars[idx].is_whitespace() {
+                    last_emoji = None;
+                }
+
+                result.push(chars[idx]);
+                idx += 1;
+                continue;
+            };
+
+            let matched = &chars[idx..idx + len];
+            match last_emoji == Some(matched) {
+                true => repeat_count += 1,
+                false => {
+                    last_emoji = Some(matched);
+                    repeat_count = 1;
+                }
+            }
+
+            if repeat_count <= repeat_limit {
+                result.push_str(name);
+            }
+
+            idx += len;
+        }
+
+        self.text = result;
+    }
+
+    pub fn remove_emoji(&mut self) {
+        let re = Regex::new(r"[^\p{L}\p{N}\p{Pd}\p{Sm}\p{Sc}]").unwrap();
+        self.text = re.replace_all(&self.text, "").to_string()
+    }
+
+    /// remove_emoji と同じだが ! と ? は抑揚に使われるので残す
+    /// (続いた記号を短くしている場合に使う)
+    pub fn remove_emoji_keep_marks(&mut self) {
+        let re = Regex::new(r"[^\p{L}\p{N}\p{Pd}\p{Sm}\p{Sc}!?！？]").unwrap();
+        self.text = re.replace_all(&self.text, "").to_string()
+    }
+
+    /// 同じ文字の繰り返しを短くする (あーーーーー -> あーーー)
+    pub fn collapse_repeat(&mut self) {
+        self.text = slang::collapse_repeat(&self.text);
+    }
+
+    /// www や 草 を笑いの読みに置換する
+    pub fn replace_laugh(&mut self, lang: Lang) {
+        self.text = slang::replace_laugh(&self.text, lang);
+    }
+
+    /// 続いた記号を短くする (!!!!!!!! -> !!)
+    pub fn limit_punctuation(&mut self) {
+        self.text = slang::limit_punctuation(&self.text);
+    }
+
+    /// 顔文字を削除する
+    pub fn remove_kaomoji(&mut self) {
+        self.text = slang::remove_kaomoji(&self.text);
+    }
+
+    /// 数、日付、時刻、通貨、単位を読み上げる形に変換する
+    pub fn replace_number(&mut self, lang: Lang) {
+        self.text = number::read_numbers(&self.text, lang);
+    }
+
+    /// 全体辞書をもとに置換する
+    pub fn replace_from_globaldict(&mut self, global_dict: &GlobalDict) {
+        let replacer = dict::global_dict_replacer(global_dict);
+
+        self.text = replacer.replace(&self.text);
+    }
+
+    /// 指定したサーバー辞書をもとに置換する
+    pub fn replace_from_guilddict(&mut self, guilddata: &GuildData) {
+        let replacer = dict::guild_dict_replacer(guilddata);
+
+        self.text = replacer.replace(&self.text);
+    }
+
+    /// 指定したユーザーの個人辞書をもとに置換する
+    pub fn replace_from_userdict(&mut self, userdata: &UserData) {
+        let replacer = dict::user_dict_replacer(userdata);
+
+        self.text = replacer.replace(&self.text);
+    }
+
+    /// 英語をカタカナ読みに変換する
+    /// (is_romaji_to_kana が true ならローマ字はひらがなにする)
+    pub fn eng_to_kana(&mut self, is_romaji_to_kana: bool) {
+        let options = ConvertOptions { is_romaji_to_kana };
+        self.text = EngToKana::convert_all_with(&self.text, options);
+    }
+
+    // ~ から始まるとなぜかエラーをはいたりするため修正
+    // っーーー や ッーーー を っ に修正
+    pub fn remove_err(&mut self) {
+        self.text = self.text.replace("~", "-").replace("～", "ー");
+
+        static RE: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"(ッ|ｯ|っ)ー+").expect("Regex Failed"));
+        self.text = RE.replace_all(&self.text, "$1").to_string();
+
+        if self.text.starts_with("ー") {
+            if let Some(s) = self.text.strip_prefix("ー") {
+                self.text = s.to_string();
+            };
+        }
+
+        if self.text.is_empty() {
+            self.text = String::from("-");
+        }
+    }
+}
+
+/// re に一致した部分を f で置換する (f が None を返した場合は置換しない)
+/// f には一致した部分の直前と直後の文字も渡す
+fn replace_with<F>(text: &str, re: &Regex, mut f: F) -> String
+where
+    F: FnMut(&Captures, Option<char>, Option<char>) -> Option<String>,
+{
+    let mut result = String::with_capacity(text.len());
+    let mut last_end = 0;
+
+    for caps in re.captures_iter(text) {
+        let m = caps.get(0).expect("Regex Failed");
+
+        let before = text[..m.start()].chars().next_back();
+        let after = text[m.end()..].chars().next();
+
+        if let Some(replaced) = f(&caps, before, after) {
+            result.push_str(&text[last_end..m.start()]);
+            result.push_str(&replaced);
+            last_end = m.end();
+        }
+    }
+
+    result.push_str(&text[last_end..]);
+    result
+}
+
+/// サーバーでの表示名を取得する (ニックネーム -> 表示名 -> ユーザー名)
+fn user_display_name(cache: &Cache, guild_id: GuildId, user_id: UserId) -> Option<String> {
+    let member_name = cache.guild(guild_id).and_then(|guild| {
+        guild
+            .members
+            .get(&user_id)
+            .map(|member| member.display_name().to_string())
+    });
+
+    if member_name.is_some() {
+        return member_name;
+    }
+
+    cache.user(user_id).map(|user| {
+        user.global_name
+            .clone()
+            .unwrap_or_else(|| user.name.clone())
+    })
+}
+
+fn channel_name(cache: &Cache, guild_id: GuildId, channel_id: ChannelId) -> Option<String> {
+    let guild = cache.guild(guild_id)?;
+
+    // スレッドは channels に含まれないため別で探す
+    match guild.channels.get(&channel_id) {
+        Some(channel) => Some(channel.name.clone()),
+        None => guild
+            .threads
+            .iter()
+            .find(|thread| thread.id == channel_id)
+            .map(|thread| thread.name.clone()),
+    }
+}
+
+/// Discord のタイムスタンプ (<t:1234567890:F> など) を読み上げる形に変換する
+fn timestamp_to_text(timestamp: i64, style: Option<&str>, lang: Lang) -> Option<String> {
+    let datetime = DateTime::from_timestamp(timestamp, 0)?;
+
+    let format = match style {
+        Some("t") => lang_t!("text.timestamp.time", lang),
+        Some("T") => lang_t!("text.timestamp.time_sec", lang),
+        Some("d") | Some("D") => lang_t!("text.timestamp.date", lang),
+        Some("R") => {
+            let diff = timestamp - Utc::now().timestamp();
+            return Some(relative_time_text(diff, lang));
+        }
+        _ => lang_t!("text.timestamp.datetime", lang),
+    };
+
+    Some(datetime.with_timezone(&Local).format(format).to_string())
+}
+
+/// 現在時刻との差を「3日前」「2時間後」のような形にする
+fn relative_time_text(diff_secs: i64, lang: Lang) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = MINUTE * 60;
+    const DAY: u64 = HOUR * 24;
+    const MONTH: u64 = DAY * 30;
+    const YEAR: u64 = DAY * 365;
+
+    let secs = diff_secs.unsigned_abs();
+
+    let amount = match secs {
+        0..MINUTE => match secs {
+            1 => format_t!("text.timestamp.second", lang, secs),
+            _ => format_t!("text.timestamp.seconds", lang, secs),
+        },
+        MINUTE..HOUR => match secs / MINUTE {
+            1 => format_t!("text.timestamp.minute", lang, 1),
+            n => format_t!("text.timestamp.minutes", lang, n),
+        },
+        HOUR..DAY => match secs / HOUR {
+            1 => format_t!("text.timestamp.hour", lang, 1),
+            n => format_t!("text.timestamp.hours", lang, n),
+        },
+        DAY..MONTH => match secs / DAY {
+            1 => format_t!("text.timestamp.day", lang, 1),
+            n => format_t!("text.timestamp.days", lang, n),
+        },
+        MONTH..YEAR => match secs / MONTH {
+            1 => format_t!("text.timestamp.month", lang, 1),
+            n => format_t!("text.timestamp.months", lang, n),
+        },
+        _ => match secs / YEAR {
+            1 => format_t!("text.timestamp.year", lang, 1),
+            n => format_t!("text.timestamp.years", lang, n),
+        },
+    };
+
+    match diff_secs < 0 {
+        true => format_t!("text.timestamp.ago", lang, amount),
+        false => format_t!("text.timestamp.later", lang, amount),
+    }
+}
@@ -0,0 +1,738 @@
+use std::sync::LazyLock;
+
+use langrustang::lang_t;
+use regex::{Captures, Regex};
+
+use super::replace_with;
+use crate::_langrustang_autogen::Lang;
+
+// 読み上げる整数部分の最大の桁数 (これより長い数は「大きな数」と読む)
+const MAX_INTEGER_DIGITS: usize = 16;
+
+// 読み上げる小数部分の最大の桁数 (これより後ろは読まない)
+const MAX_DECIMAL_DIGITS: usize = 6;
+
+// (記号, 日本語読み, 英語読み (単数), 英語読み (複数))
+// 数の後ろに付く単位。長いものから順に照合する
+const UNITS: &[(&str, &str, &str, &str)] = &[
+    ("%", "パーセント", "percent", "percent"),
+    ("％", "パーセント", "percent", "percent"),
+    ("円", "円", "yen", "yen"),
+    (
+        "km/h",
+        "キロメートル毎時",
+        "kilometer per hour",
+        "kilometers per hour",
+    ),
+    ("km", "キロメートル", "kilometer", "kilometers"),
+    ("cm", "センチメートル", "centimeter", "centimeters"),
+    ("mm", "ミリメートル", "millimeter", "millimeters"),
+    ("m", "メートル", "meter", "meters"),
+    ("kg", "キログラム", "kilogram", "kilograms"),
+    ("mg", "ミリグラム", "milligram", "milligrams"),
+    ("g", "グラム", "gram", "grams"),
+    ("mL", "ミリリットル", "milliliter", "milliliters"),
+    ("ml", "ミリリットル", "milliliter", "milliliters"),
+    ("L", "リットル", "liter", "liters"),
+    ("kcal", "キロカロリー", "kilocalorie", "kilocalories"),
+    ("℃", "度", "degree Celsius", "degrees Celsius"),
+    ("°C", "度", "degree Celsius", "degrees Celsius"),
+    ("°", "度", "degree", "degrees"),
+    ("KB", "キロバイト", "kilobyte", "kilobytes"),
+    ("MB", "メガバイト", "megabyte", "megabytes"),
+    ("GB", "ギガバイト", "gigabyte", "gigabytes"),
+    ("TB", "テラバイト", "terabyte", "terabytes"),
+    ("GHz", "ギガヘルツ", "gigahertz", "gigahertz"),
+    ("MHz", "メガヘルツ", "megahertz", "megahertz"),
+    ("Hz", "ヘルツ", "hertz", "hertz"),
+    (
+        "fps",
+        "エフピーエス",
+        "frame per second",
+        "frames per second",
+    ),
+    ("ms", "ミリ秒", "millisecond", "milliseconds"),
+];
+
+// (記号, 日本語読み, 英語読み (単数), 英語読み (複数))
+// 数の前に付く通貨記号
+const CURRENCIES: &[(&str, &str, &str, &str)] = &[
+    ("¥", "円", "yen", "yen"),
+    ("￥", "円", "yen", "yen"),
+    ("$", "ドル", "dollar", "dollars"),
+    ("€", "ユーロ", "euro", "euros"),
+    ("£", "ポンド", "pound", "pounds"),
+];
+
+// (文字, 分子, 分母)
+const FRACTIONS: &[(char, u64, u64)] = &[
+    ('½', 1, 2),
+    ('⅓', 1, 3),
+    ('⅔', 2, 3),
+    ('¼', 1, 4),
+    ('¾', 3, 4),
+];
+
+const JA_DIGITS: [&str; 10] = ["ゼロ", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+// 4桁ごとの位
+const JA_GROUPS: [&str; 4] = ["", "万", "億", "兆"];
+
+const EN_ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const EN_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+// 3桁ごとの位
+const EN_GROUPS: [&str; 6] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+];
+
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// 日付 (2024-03-14, 2024/3/14, 3/14) と分数 (1/50)
+/// (年のない a/b は日付として読めない場合 (1/50) のみ分数とする)
+static DATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?<iso_year>[0-9]{4})-(?<iso_month>[0-9]{1,2})-(?<iso_day>[0-9]{1,2})|(?:(?<year>[0-9]{4})/)?(?<month>[0-9]{1,3})/(?<day>[0-9]{1,3})",
+    )
+    .expect("Regex Failed")
+});
+
+/// 分数の斜線 (U+2044) を使った分数 (1⁄2)
+static FRACTION_SLASH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?<numerator>[0-9]+)\u{2044}(?<denominator>[0-9]+)").expect("Regex Failed")
+});
+
+/// 時刻 (12:30, 12:30:15)
+static TIME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?<hour>[0-9]{1,2}):(?<minute>[0-9]{2})(?::(?<second>[0-9]{2}))?")
+        .expect("Regex Failed")
+});
+
+/// 通貨記号、単位付きの数 (¥1,234, -5℃, 3.14, 50%)
+static NUMBER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    let escape = |symbols: &[(&str, &str, &str, &str)]| {
+        let mut symbols: Vec<&str> = symbols.iter().map(|(symbol, ..)| *symbol).collect();
+        symbols.sort_by_key(|symbol| std::cmp::Reverse(symbol.len()));
+
+        symbols
+            .into_iter()
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join("|")
+    };
+
+    Regex::new(&format!(
+        r"(?<currency>{})?(?<minus>-)?(?<number>[0-9]{{1,3}}(?:,[0-9]{{3}})+(?:\.[0-9]+)?|[0-9]+(?:\.[0-9]+)*)(?<unit>{})?",
+        escape(CURRENCIES),
+        escape(UNITS),
+    ))
+    .expect("Regex Failed")
+});
+
+/// 文章中の数、日付、時刻、通貨、単位を読み上げる形に変換する
+pub(super) fn read_numbers(text: &str, lang: Lang) -> String {
+    let text = replace_fractions(text, lang);
+    let text = replace_with(&text, &DATE_RE, |caps, before, after| {
+        is_isolated(before, after)
+            .then(|| read_date(caps, lang))
+            .flatten()
+    });
+    let text = replace_with(&text, &TIME_RE, |caps, before, after| {
+        is_isolated(before, after)
+            .then(|| read_time(caps, lang))
+            .flatten()
+    });
+
+    replace_with(&text, &NUMBER_RE, |caps, before, after| {
+        read_number(caps, before, after, lang)
+    })
+}
+
+/// 日付や時刻の前後に数字や区切り文字が続いていないか
+/// (1/2/3 や 123:456 のような文字列の一部を読まないようにする)
+fn is_isolated(before: Option<char>, after: Option<char>) -> bool {
+    !before.is_some_and(|c| c.is_ascii_digit() || "/:.".contains(c))
+        && !after.is_some_and(|c| c.is_ascii_digit() || "/:".contains(c))
+}
+
+/// ½ のような分数の文字と 1⁄2 のような分数の斜線を使った分数を読む
+fn replace_fractions(text: &str, lang: Lang) -> String {
+    let text = FRACTION_SLASH_RE.replace_all(text, |caps: &Captures| {
+        let get = |name: &str| caps[name].parse::<u64>().ok();
+
+        match (get("numerator"), get("denominator")) {
+            (Some(numerator), Some(denominator)) if denominator != 0 => {
+                fraction_text(numerator, denominator, lang)
+            }
+            _ => caps[0].to_string(),
+        }
+    });
+
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match FRACTIONS.iter().find(|(fraction, ..)| *fraction == c) {
+            Some((_, numerator, denominator)) => {
+                result.push_str(&fraction_text(*numerator, *denominator, lang))
+            }
+            None => result.push(c),
+        }
+    }
+
+    result
+}
+
+fn read_date(caps: &Captures, lang: Lang) -> Option<String> {
+    let get = |name| caps.name(name).and_then(|m| m.as_str().parse::<u64>().ok());
+
+    let year = get("iso_year").or_else(|| get("year"));
+    let month = get("iso_month").or_else(|| get("month"))?;
+    let day = get("iso_day").or_else(|| get("day"))?;
+
+    let is_date = (1..=12).contains(&month) && (1..=31).contains(&day);
+
+    // 日付として読めない 年のない a/b は分数として読む
+    if !is_date {
+        return match year {
+            None if day != 0 => Some(fraction_text(month, day, lang)),
+            _ => None,
+        };
+    }
+
+    let text = match lang {
+        Lang::Ja => {
+            let date = format!("{}月{}日", ja_integer(month), ja_integer(day));
+
+            match year {
+                Some(year) => format!("{}年{date}", ja_integer(year)),
+                None => date,
+            }
+        }
+        Lang::En => {
+            let date = format!("{} {}", EN_MONTHS[month as usize - 1], en_ordinal(day));
+
+            match year {
+                Some(year) => format!("{date}, {}", en_year(year)),
+                None => date,
+            }
+        }
+    };
+
+    Some(text)
+}
+
+fn read_time(caps: &Captures, lang: Lang) -> Option<String> {
+    let get = |name| caps.name(name).and_then(|m| m.as_str().parse::<u64>().ok());
+
+    let hour = get("hour")?;
+    let minute = get("minute")?;
+    let second = get("second");
+
+    // 深夜の 25:00 のような書き方も時刻として読む
+    if hour > 30 || minute > 59 || second.is_some_and(|second| second > 59) {
+        return None;
+    }
+
+    let text = match lang {
+        Lang::Ja => {
+            let mut text = format!("{}時", ja_integer(hour));
+
+            if minute != 0 || second.is_some() {
+                text.push_str(&format!("{}分", ja_integer(minute)));
+            }
+            if let Some(second) = second {
+                text.push_str(&format!("{}秒", ja_integer(second)));
+            }
+
+            text
+        }
+        Lang::En => {
+            let mut text = match minute {
+                0 => format!("{} o'clock", en_integer(hour)),
+                1..10 => format!("{} oh {}", en_integer(hour), en_integer(minute)),
+                _ => format!("{} {}", en_integer(hour), en_integer(minute)),
+            };
+
+            if let Some(second) = second {
+                let unit = match second {
+                    1 => "second",
+                    _ => "seconds",
+                };
+                text.push_str(&format!(" and {} {unit}", en_integer(second)));
+            }
+
+            text
+        }
+    };
+
+    Some(text)
+}
+
+fn read_number(
+    caps: &Captures,
+    before: Option<char>,
+    after: Option<char>,
+    lang: Lang,
+) -> Option<String> {
+    let number = caps.name("number")?.as_str().replace(",", "");
+    let currency = caps.name("currency").and_then(|currency| {
+        CURRENCIES
+            .iter()
+            .find(|(symbol, ..)| *symbol == currency.as_str())
+    });
+
+    let is_en = matches!(lang, Lang::En);
+    let mut text = String::new();
+
+    // 英数字の直後の - (a-1 など) はハイフンとして残す
+    let is_hyphen = caps.name("minus").is_some()
+        && currency.is_none()
+        && before.is_some_and(|c| c.is_ascii_alphanumeric());
+
+    // 英単語とつながらないように空白を入れる
+    if is_en && !is_hyphen && before.is_some_and(|c| c.is_ascii_alphabetic()) {
+        text.push(' ');
+    }
+
+    if caps.name("minus").is_some() {
+        match (is_hyphen, lang) {
+            (true, _) => text.push('-'),
+            (false, Lang::Ja) => text.push_str("マイナス"),
+            (false, Lang::En) => text.push_str("minus "),
+        }
+    }
+
+    text.push_str(&number_text(&number, lang));
+
+    // 英字の単位の直後に英字が続く場合 (5min など) は単位として読まない
+    let unit_symbol = caps.name("unit").map(|unit| unit.as_str());
+    let unit = unit_symbol.and_then(|symbol| {
+        let is_word = symbol.chars().all(|c| c.is_ascii_alphabetic());
+
+        UNITS
+            .iter()
+            .find(|(unit_symbol, ..)| *unit_symbol == symbol)
+            .filter(|_| !is_word || !after.is_some_and(|c| c.is_ascii_alphabetic()))
+    });
+
+    for (_, ja, en_singular, en_plural) in unit.into_iter().chain(currency) {
+        match lang {
+            Lang::Ja => text.push_str(ja),
+            Lang::En => {
+                text.push(' ');
+                text.push_str(if number == "1" {
+                    en_singular
+                } else {
+                    en_plural
+                });
+            }
+        }
+    }
+
+    // 単位として読まなかった文字はそのまま残す
+    if let (None, Some(symbol)) = (unit, unit_symbol) {
+        if is_en {
+            text.push(' ');
+        }
+        text.push_str(symbol);
+    } else if is_en && after.is_some_and(|c| c.is_ascii_alphabetic()) {
+        text.push(' ');
+    }
+
+    Some(text)
+}
+
+/// "1234.5" や "1.2.3" を読む
+fn number_text(number: &str, lang: Lang) -> String {
+    let separator = match lang {
+        Lang::Ja => "点",
+        Lang::En => " point ",
+    };
+
+    let mut parts = number.split('.');
+    let integer = parts.next().unwrap_or_default();
+    let mut text = integer_text(integer, lang);
+
+    let rest: Vec<&str> = parts.collect();
+
+    match rest.as_slice() {
+        // 小数は小数点以下を1桁ずつ読む
+        [decimal] => {
+            text.push_str(separator);
+
+            let digits = decimal.chars().take(MAX_DECIMAL_DIGITS).map(|c| {
+                let digit = c.to_digit(10).unwrap_or_default() as usize;
+
+                match lang {
+                    Lang::Ja => JA_DIGITS[digit],
+                    Lang::En => EN_ONES[digit],
+                }
+            });
+
+            let digits: Vec<&str> = digits.collect();
+            text.push_str(&digits.join(if let Lang::En = lang { " " } else { "" }));
+        }
+        // バージョン番号などは区切りごとに整数として読む
+        parts => {
+            for part in parts {
+                text.push_str(separator);
+                text.push_str(&integer_text(part, lang));
+            }
+        }
+    }
+
+    text
+}
+
+/// 数字だけの文字列を読む (0 から始まる場合は1桁ずつ読む)
+fn integer_text(integer: &str, lang: Lang) -> String {
+    if integer.len() > MAX_INTEGER_DIGITS {
+        return lang_t!("text.number.too_large", lang).to_string();
+    }
+
+    if integer.len() > 1 && integer.starts_with('0') {
+        let digits: Vec<&str> = integer
+            .chars()
+            .map(|c| {
+                let digit = c.to_digit(10).unwrap_or_default() as usize;
+
+                match lang {
+                    Lang::Ja => JA_DIGITS[digit],
+                    Lang::En => EN_ONES[digit],
+                }
+            })
+            .collect();
+
+        return digits.join(if let Lang::En = lang { " " } else { "" });
+    }
+
+    let Ok(n) = integer.parse::<u64>() else {
+        return integer.to_string();
+    };
+
+    match lang {
+        Lang::Ja => ja_integer(n),
+        Lang::En => en_integer(n),
+    }
+}
+
+/// 分数を読む (日本語は「三分の一」、英語は "one third")
+fn fraction_text(numerator: u64, denominator: u64, lang: Lang) -> String {
+    match lang {
+        Lang::Ja => format!("{}分の{}", ja_integer(denominator), ja_integer(numerator)),
+        Lang::En => {
+            let denominator = match (denominator, numerator) {
+                (2, 1) => "half".to_string(),
+                (2, _) => "halves".to_string(),
+                (4, 1) => "quarter".to_string(),
+                (4, _) => "quarters".to_string(),
+                (_, 1) => en_ordinal(denominator),
+                _ => format!("{}s", en_ordinal(denominator)),
+            };
+
+            format!("{} {denominator}", en_integer(numerator))
+        }
+    }
+}
+
+/// 漢数字で読む (1234 -> "千二百三十四")
+fn ja_integer(n: u64) -> String {
+    if n == 0 {
+        return JA_DIGITS[0].to_string();
+    }
+
+    let mut text = String::new();
+    let mut groups = vec![];
+    let mut rest = n;
+
+    while rest > 0 {
+        groups.push(rest % 10_000);
+        rest /= 10_000;
+    }
+
+    for (i, group) in groups.iter().enumerate().rev() {
+        if *group == 0 {
+            continue;
+        }
+
+        for (digit, unit) in [
+            (group / 1000, "千"),
+            (group / 100 % 10, "百"),
+            (group / 10 % 10, "十"),
+        ] {
+            match digit {
+                0 => (),
+                1 => text.push_str(unit),
+                _ => {
+                    text.push_str(JA_DIGITS[digit as usize]);
+                    text.push_str(unit);
+                }
+            }
+        }
+
+        if group % 10 != 0 {
+            text.push_str(JA_DIGITS[(group % 10) as usize]);
+        }
+
+        text.push_str(JA_GROUPS.get(i).copied().unwrap_or_default());
+    }
+
+    text
+}
+
+/// 英語で読む (1234 -> "one thousand two hundred thirty-four")
+fn en_integer(n: u64) -> String {
+    if n == 0 {
+        return EN_ONES[0].to_string();
+    }
+
+    let mut words = vec![];
+    let mut groups = vec![];
+    let mut rest = n;
+
+    while rest > 0 {
+        groups.push(rest % 1000);
+        rest /= 1000;
+    }
+
+    for (i, group) in groups.iter().enumerate().rev() {
+        if *group == 0 {
+            continue;
+        }
+
+        if group / 100 != 0 {
+            words.push(format!("{} hundred", EN_ONES[(group / 100) as usize]));
+        }
+
+        match group % 100 {
+            0 => (),
+            n @ 1..20 => words.push(EN_ONES[n as usize].to_string()),
+            n if n % 10 == 0 => words.push(EN_TENS[(n / 10) as usize].to_string()),
+            n => words.push(format!(
+                "{}-{}",
+                EN_TENS[(n / 10) as usize],
+                EN_ONES[(n % 10) as usize]
+            )),
+        }
+
+        if let Some(group_name) = EN_GROUPS.get(i).filter(|name| !name.is_empty()) {
+            words.push(group_name.to_string());
+        }
+    }
+
+    words.join(" ")
+}
+
+/// 序数で読む (14 -> "fourteenth")
+fn en_ordinal(n: u64) -> String {
+    let cardinal = en_integer(n);
+
+    // 最後の単語だけ序数にする
+    let (head, last) = match cardinal.rfind([' ', '-']) {
+        Some(idx) => cardinal.split_at(idx + 1),
+        None => ("", cardinal.as_str()),
+    };
+
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        last if last.ends_with('y') => format!("{}ieth", &last[..last.len() - 1]),
+        last => format!("{last}th"),
+    };
+
+    format!("{head}{last}")
+}
+
+/// 年を読む (2024 -> "twenty twenty-four")
+fn en_year(year: u64) -> String {
+    if !(1100..10000).contains(&year) || (2000..2010).contains(&year) || year.is_multiple_of(1000) {
+        return en_integer(year);
+    }
+
+    match year % 100 {
+        0 => format!("{} hundred", en_integer(year / 100)),
+        n @ 1..10 => format!("{} oh {}", en_integer(year / 100), en_integer(n)),
+        n => format!("{} {}", en_integer(year / 100), en_integer(n)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(read_numbers("1234", Lang::Ja), "千二百三十四");
+        assert_eq!(read_numbers("10000", Lang::Ja), "一万");
+        assert_eq!(read_numbers("りんごが3個", Lang::Ja), "りんごが三個");
+        assert_eq!(read_numbers("007", Lang::Ja), "ゼロゼロ七");
+
+        assert_eq!(
+            read_numbers("1234", Lang::En),
+            "one thousand two hundred thirty-four"
+        );
+        assert_eq!(
+            read_numbers("I have 3 apples", Lang::En),
+            "I have three apples"
+        );
+        assert_eq!(read_numbers("1,000,000", Lang::En), "one million");
+
+        // 長すぎる数は「大きな数」と読む
+        assert_eq!(
+            read_numbers("12345678901234567", Lang::Ja),
+            lang_t!("text.number.too_large", Lang::Ja)
+        );
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(read_numbers("3.14", Lang::Ja), "三点一四");
+        assert_eq!(read_numbers("3.14", Lang::En), "three point one four");
+
+        // バージョン番号は区切りごとに読む
+        assert_eq!(read_numbers("1.2.3", Lang::Ja), "一点二点三");
+    }
+
+    #[test]
+    fn test_negative() {
+        assert_eq!(read_numbers("気温は-3", Lang::Ja), "気温はマイナス三");
+        assert_eq!(read_numbers("-3", Lang::En), "minus three");
+
+        // 英数字の直後の - はハイフンとして残す
+        assert_eq!(read_numbers("a-1", Lang::En), "a-one");
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(read_numbers("3/14", Lang::Ja), "三月十四日");
+        assert_eq!(read_numbers("1/2", Lang::Ja), "一月二日");
+        assert_eq!(read_numbers("3/4", Lang::Ja), "三月四日");
+        assert_eq!(read_numbers("12/5", Lang::Ja), "十二月五日");
+        assert_eq!(read_numbers("1/05", Lang::Ja), "一月五日");
+        assert_eq!(
+            read_numbers("2024/3/14", Lang::Ja),
+            "二千二十四年三月十四日"
+        );
+        assert_eq!(
+            read_numbers("2024-03-14", Lang::Ja),
+            "二千二十四年三月十四日"
+        );
+
+        assert_eq!(read_numbers("3/14", Lang::En), "March fourteenth");
+        assert_eq!(
+            read_numbers("2024-03-14", Lang::En),
+            "March fourteenth, twenty twenty-four"
+        );
+    }
+
+    #[test]
+    fn test_fraction() {
+        // 日付として読めない場合と、分数の文字や分数の斜線を使った場合は分数
+        assert_eq!(read_numbers("1/50", Lang::Ja), "五十分の一");
+        assert_eq!(read_numbers("13/4", Lang::Ja), "四分の十三");
+        assert_eq!(read_numbers("½", Lang::Ja), "二分の一");
+        assert_eq!(read_numbers("1\u{2044}3", Lang::Ja), "三分の一");
+
+        assert_eq!(read_numbers("½", Lang::En), "one half");
+        assert_eq!(read_numbers("2\u{2044}3", Lang::En), "two thirds");
+
+        // 1/2/3 のような文字列の一部は日付や分数として読まない
+        assert_eq!(read_numbers("1/2/3", Lang::Ja), "一/二/三");
+    }
+
+    #[test]
+    fn test_time() {
+        assert_eq!(read_numbers("12:30", Lang::Ja), "十二時三十分");
+        assert_eq!(read_numbers("9:00", Lang::Ja), "九時");
+        assert_eq!(read_numbers("25:00", Lang::Ja), "二十五時");
+
+        assert_eq!(read_numbers("9:05", Lang::En), "nine oh five");
+        assert_eq!(read_numbers("9:00", Lang::En), "nine o'clock");
+        assert_eq!(
+            read_numbers("12:30:15", Lang::En),
+            "twelve thirty and fifteen seconds"
+        );
+    }
+
+    #[test]
+    fn test_currency() {
+        assert_eq!(read_numbers("¥1,234", Lang::Ja), "千二百三十四円");
+        assert_eq!(read_numbers("$5", Lang::Ja), "五ドル");
+
+        assert_eq!(read_numbers("$1", Lang::En), "one dollar");
+        assert_eq!(read_numbers("$5", Lang::En), "five dollars");
+    }
+
+    #[test]
+    fn test_unit() {
+        assert_eq!(read_numbers("50%", Lang::Ja), "五十パーセント");
+        assert_eq!(read_numbers("5kg", Lang::Ja), "五キログラム");
+        assert_eq!(read_numbers("-5℃", Lang::Ja), "マイナス五度");
+
+        assert_eq!(read_numbers("1km", Lang::En), "one kilometer");
+        assert_eq!(read_numbers("2GB", Lang::En), "two gigabytes");
+
+        // 英単語の一部 (5min の m) は単位として読まない
+        assert_eq!(read_numbers("5min", Lang::Ja), "五min");
+    }
+
+    #[test]
+    fn test_en_ordinal_and_year() {
+        assert_eq!(en_ordinal(1), "first");
+        assert_eq!(en_ordinal(12), "twelfth");
+        assert_eq!(en_ordinal(21), "twenty-first");
+        assert_eq!(en_ordinal(30), "thirtieth");
+
+        assert_eq!(en_year(2024), "twenty twenty-four");
+        assert_eq!(en_year(2005), "two thousand five");
+        assert_eq!(en_year(1905), "nineteen oh five");
+        assert_eq!(en_year(1900), "nineteen hundred");
+    }
+}
@@ -0,0 +1,127 @@
+use serenity::all::{Cache, GuildId};
+use sonorust_db::{GlobalDict, GuildData, PipelineStage, UserData};
+
+use super::TextReplace;
+use crate::_langrustang_autogen::Lang;
+
+/// 各段階で使うデータ
+pub struct PipelineContext<'a> {
+    pub cache: &'a Cache,
+    pub guild_id: GuildId,
+    pub guilddata: &'a GuildData,
+    pub userdata: &'a UserData,
+    pub global_dict: &'a GlobalDict,
+    pub lang: Lang,
+    pub emoji_repeat_limit: u32,
+}
+
+impl TextReplace {
+    /// サーバーの順番で有効な段階を実行する
+    /// on_stage には段階ごとに実行後の文字を渡す
+    pub fn run_pipeline<F>(&mut self, context: &PipelineContext, mut on_stage: F)
+    where
+        F: FnMut(PipelineStage, &str),
+    {
+        for stage in context.guilddata.pipeline.enabled_stages() {
+            self.apply_stage(stage, context);
+            on_stage(stage, &self.text);
+        }
+    }
+
+    /// 1つの段階を実行する
+    pub fn apply_stage(&mut self, stage: PipelineStage, context: &PipelineContext) {
+        let options = &context.guilddata.options;
+        let lang = context.lang;
+
+        match stage {
+            PipelineStage::Codeblock => self.remove_codeblock(lang),
+            PipelineStage::Markdown => self.strip_markdown(lang, options.is_skip_spoiler),
+            PipelineStage::FixError => self.remove_err(),
+            PipelineStage::Url => self.remove_url(lang, options.is_read_url_domain),
+            PipelineStage::DiscordObject => {
+                self.replace_discord_obj(context.cache, context.guild_id, lang)
+            }
+            PipelineStage::GlobalDict => self.replace_from_globaldict(context.global_dict),
+            PipelineStage::GuildDict => self.replace_from_guilddict(context.guilddata),
+            PipelineStage::UserDict => self.replace_from_userdict(context.userdata),
+            PipelineStage::Kaomoji => self.remove_kaomoji(),
+            PipelineStage::Laugh => self.replace_laugh(lang),
+            PipelineStage::CollapseRepeat => self.collapse_repeat(),
+            PipelineStage::LimitPunctuation => self.limit_punctuation(),
+            PipelineStage::Emoji => self.replace_emoji(lang, context.emoji_repeat_limit),
+            PipelineStage::Number => self.replace_number(lang),
+            // 日本語の時のみ英語を日本語読みに変換
+            PipelineStage::EngToKana => {
+                if let Lang::Ja = lang {
+                    self.eng_to_kana(options.is_romaji_to_kana);
+                }
+            }
+            // 続いた記号を短くしている場合は残した ! と ? を消さない
+            PipelineStage::RemoveSymbol => {
+                if context
+                    .guilddata
+                    .pipeline
+                    .is_enabled(PipelineStage::LimitPunctuation)
+                {
+                    self.remove_emoji_keep_marks();
+                } else {
+                    self.remove_emoji();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn run(guilddata: &GuildData, text: &str) -> String {
+        let cache = Cache::new();
+        let userdata = UserData {
+            user_id: 1.into(),
+            model_name: String::new(),
+            speaker_name: String::new(),
+            style_name: String::new(),
+            length: 1.0,
+            read_name: None,
+            is_read_disabled: false,
+            dict: HashMap::new(),
+            dict_version: 0,
+        };
+        let global_dict = GlobalDict {
+            dict: HashMap::new(),
+            dict_version: 0,
+        };
+        let context = PipelineContext {
+            cache: &cache,
+            guild_id: guilddata.guild_id,
+            guilddata,
+            userdata: &userdata,
+            global_dict: &global_dict,
+            lang: Lang::Ja,
+            emoji_repeat_limit: 3,
+        };
+
+        let mut text_replace = TextReplace::new(text);
+        text_replace.run_pipeline(&context, |_, _| {});
+        text_replace.as_string()
+    }
+
+    #[test]
+    fn test_limit_punctuation() {
+        let mut guilddata = GuildData::default_settings(GuildId::new(1));
+
+        // OFF なら今まで通り記号は全て消える
+        assert_eq!(run(&guilddata, "すごい!!!!!!!!"), "すごい");
+
+        guilddata
+            .pipeline
+            .set_enabled(PipelineStage::LimitPunctuation, true);
+        assert_eq!(run(&guilddata, "すごい!!!!!!!!"), "すごい!!");
+        assert_eq!(run(&guilddata, "ほんと？？？？"), "ほんと？？");
+        assert_eq!(run(&guilddata, "えっと。。。。"), "えっと");
+    }
+}
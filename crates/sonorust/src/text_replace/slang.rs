@@ -0,0 +1,189 @@
+use std::sync::LazyLock;
+
+use langrustang::lang_t;
+use regex::{Captures, Regex};
+
+use super::replace_with;
+use crate::_langrustang_autogen::Lang;
+
+// 同じ文字が続いた場合に残す最大の数
+const REPEAT_LIMIT: usize = 3;
+
+// 記号が続いた場合に残す最大の数
+const PUNCTUATION_LIMIT: usize = 2;
+
+// 括弧の中に含まれていれば顔文字とみなす文字
+// (英語やコードの括弧を消さないように ASCII の記号は含めない)
+const FACE_CHARS: &str = "´｀＾・ω∀Дд▽∇°ﾟ≧≦＞＜；＿＊ε○◕‿╹◡";
+
+// ASCII だけで書かれる顔文字 (括弧で囲まれていても顔文字とみなす)
+const SIMPLE_KAOMOJI: &[&str] = &[
+    "m(_ _)m", "^_^;", "^^;", "^_^", "^o^", "^^", ">_<", "><", "T_T", ";_;", "orz",
+];
+
+/// 笑いを表す w (wwww, それなw)
+static LAUGH_W_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[wWｗＷ]+").expect("Regex Failed"));
+
+/// 笑いを表す 草、笑、(笑)、大草原
+static LAUGH_KANJI_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?<phrase>[\(（]笑[\)）]|大草原)|[草笑]+").expect("Regex Failed")
+});
+
+/// ASCII だけで書かれる顔文字 (長いものから順に照合する)
+static SIMPLE_KAOMOJI_RE: LazyLock<Regex> = LazyLock::new(|| {
+    let mut kaomoji = SIMPLE_KAOMOJI.to_vec();
+    kaomoji.sort_by_key(|kaomoji| std::cmp::Reverse(kaomoji.len()));
+
+    let pattern = kaomoji
+        .into_iter()
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join("|");
+
+    Regex::new(&pattern).expect("Regex Failed")
+});
+
+/// 括弧で囲まれた顔文字 (腕も含む)
+static KAOMOJI_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[ヽ٩＼\\┐⊂]?[\(（](?<face>[^\(\)（）\n]{1,15})[\)）][ノﾉ۶／/┌⊃ｼ]*")
+        .expect("Regex Failed")
+});
+
+static PUNCTUATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"[\p{{P}}\p{{Sm}}]{{{},}}", PUNCTUATION_LIMIT + 1)).expect("Regex Failed")
+});
+
+/// 同じ文字が REPEAT_LIMIT 個より多く続いた場合は REPEAT_LIMIT 個にする
+/// (数字は桁が変わるのでそのまま残す)
+pub(super) fn collapse_repeat(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = None;
+    let mut repeat_count = 0;
+
+    for c in text.chars() {
+        match last == Some(c) {
+            true => repeat_count += 1,
+            false => {
+                last = Some(c);
+                repeat_count = 1;
+            }
+        }
+
+        if repeat_count <= REPEAT_LIMIT || c.is_ascii_digit() {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// www や 草 などの笑いを表す文字を読みに変換する
+pub(super) fn replace_laugh(text: &str, lang: Lang) -> String {
+    let laugh = lang_t!("text.laugh", lang);
+
+    // 英単語の一部の w は置換しない
+    // 1文字の w は日本語の直後 (それなw) か、w だけの場合のみ置換する
+    let text = replace_with(text, &LAUGH_W_RE, |caps, before, after| {
+        let len = caps.get(0)?.as_str().chars().count();
+
+        let is_in_word = before.is_some_and(|c| c.is_ascii_alphanumeric())
+            || after.is_some_and(|c| c.is_ascii_alphanumeric());
+        let is_laugh = len >= 2 || !before.is_some_and(|c| c.is_ascii());
+
+        (!is_in_word && is_laugh).then(|| laugh.to_string())
+    });
+
+    // 「草むしり」「笑顔」のような熟語は置換しない
+    replace_with(&text, &LAUGH_KANJI_RE, |caps: &Captures, before, after| {
+        let is_phrase = caps.name("phrase").is_some();
+
+        let is_after_kanji = before.is_some_and(is_kanji);
+        let is_end = !after.is_some_and(|c| !c.is_whitespace() && !is_punctuation(c));
+
+        (is_phrase || (!is_after_kanji && is_end)).then(|| laugh.to_string())
+    })
+}
+
+/// 記号が PUNCTUATION_LIMIT 個より多く続いた場合は PUNCTUATION_LIMIT 個にする
+pub(super) fn limit_punctuation(text: &str) -> String {
+    PUNCTUATION_RE
+        .replace_all(text, |caps: &Captures| {
+            caps[0].chars().take(PUNCTUATION_LIMIT).collect::<String>()
+        })
+        .to_string()
+}
+
+/// 顔文字を削除する
+pub(super) fn remove_kaomoji(text: &str) -> String {
+    // ひらがなや漢字を含む括弧 ((笑) や (注) など) と
+    // 英数字を含む括弧 ((it's late) や (x > 0) など) は顔文字として扱わない
+    let text = replace_with(text, &KAOMOJI_RE, |caps, _, _| {
+        let face = caps.name("face")?.as_str();
+
+        let is_kaomoji = SIMPLE_KAOMOJI.contains(&face)
+            || (face.chars().any(|c| FACE_CHARS.contains(c))
+                && !face
+                    .chars()
+                    .any(|c| is_kanji(c) || is_hiragana(c) || c.is_ascii_alphanumeric()));
+
+        is_kaomoji.then(String::new)
+    });
+
+    // 単語の一部 (orzo や x^^2 など) は削除しない
+    replace_with(&text, &SIMPLE_KAOMOJI_RE, |_, before, after| {
+        let is_in_word = before.is_some_and(|c| c.is_ascii_alphanumeric())
+            || after.is_some_and(|c| c.is_ascii_alphanumeric());
+
+        (!is_in_word).then(String::new)
+    })
+}
+
+fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '々')
+}
+
+fn is_hiragana(c: char) -> bool {
+    matches!(c, '\u{3041}'..='\u{309F}')
+}
+
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || matches!(c, '、' | '。' | '！' | '？' | '…' | '・')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_kaomoji() {
+        assert_eq!(remove_kaomoji("おはよう(´・ω・`)"), "おはよう");
+        assert_eq!(remove_kaomoji("ヽ(・∀・)ノ"), "");
+        assert_eq!(remove_kaomoji("(＞＜)"), "");
+        assert_eq!(remove_kaomoji("(*^▽^*)"), "");
+        assert_eq!(remove_kaomoji("ありがとう(^_^)"), "ありがとう");
+        assert_eq!(remove_kaomoji("すみません m(_ _)m"), "すみません ");
+        assert_eq!(remove_kaomoji("負けた orz"), "負けた ");
+        assert_eq!(remove_kaomoji("やった^^"), "やった");
+    }
+
+    #[test]
+    fn test_remove_kaomoji_false_positive() {
+        // 普通の括弧は残す
+        for text in [
+            "(it's late)",
+            "(foo_bar)",
+            "(x > 0)",
+            "(a*b)",
+            "(25°C)",
+            "それな(笑)",
+        ] {
+            assert_eq!(remove_kaomoji(text), text);
+        }
+
+        // 単語や式の一部は残す
+        assert_eq!(remove_kaomoji("orzo"), "orzo");
+        assert_eq!(remove_kaomoji("x^^2"), "x^^2");
+        assert_eq!(remove_kaomoji("<a><b>"), "<a><b>");
+    }
+}
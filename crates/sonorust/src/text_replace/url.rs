@@ -0,0 +1,123 @@
+use std::sync::LazyLock;
+
+use langrustang::lang_t;
+use regex::{Captures, Regex};
+
+use crate::_langrustang_autogen::Lang;
+
+// 2つのラベルで1つのトップレベルドメインとして扱うもの
+// (example.co.jp は co.jp ではなく example.co.jp を読む)
+const SECOND_LEVEL_SUFFIXES: &[&str] = &[
+    "co.jp", "ne.jp", "or.jp", "ac.jp", "go.jp", "ed.jp", "gr.jp", "lg.jp", "co.uk", "org.uk",
+    "ac.uk", "gov.uk", "com.au", "net.au", "org.au", "co.kr", "or.kr", "com.cn", "com.tw",
+    "com.br", "co.nz", "co.in",
+];
+
+/// URL (<> で囲まれた埋め込みなしのリンクも含む)
+static URL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<?https?://(?<host>[\w\-\.]+)(?::\d+)?[\w/:%#\$&\?\(\)~\.=\+\-]*>?")
+        .expect("Regex Failed")
+});
+
+/// URL を置換する
+/// is_read_domain が true ならドメイン名を読み、false なら「URL」と読む
+pub(super) fn replace_url(text: &str, lang: Lang, is_read_domain: bool) -> String {
+    URL_RE
+        .replace_all(text, |caps: &Captures| {
+            let domain = caps
+                .name("host")
+                .and_then(|host| registrable_domain(host.as_str()))
+                .filter(|_| is_read_domain);
+
+            match domain {
+                Some(domain) => domain_text(domain, lang),
+                None => lang_t!("text.url", lang).to_string(),
+            }
+        })
+        .to_string()
+}
+
+/// サブドメインを除いたドメイン名を返す (gist.github.com -> github.com)
+fn registrable_domain(host: &str) -> Option<&str> {
+    let host = host.trim_end_matches('.');
+    let labels: Vec<&str> = host.split('.').collect();
+
+    if labels.len() < 2 || labels.iter().any(|label| label.is_empty()) {
+        return None;
+    }
+
+    // IP アドレスはドメイン名として読まない
+    if labels
+        .iter()
+        .all(|label| label.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+
+    let last_two = labels[labels.len() - 2..].join(".").to_ascii_lowercase();
+    let label_count = match SECOND_LEVEL_SUFFIXES.contains(&last_two.as_str()) {
+        true => 3,
+        false => 2,
+    };
+
+    let skip = labels.len().checked_sub(label_count)?;
+    let start: usize = labels[..skip].iter().map(|label| label.len() + 1).sum();
+
+    Some(&host[start..])
+}
+
+/// ドメイン名を読む形にする
+/// 日本語はトップレベルドメインを除いた名前 (github)、英語は "github dot com"
+fn domain_text(domain: &str, lang: Lang) -> String {
+    match lang {
+        Lang::Ja => domain.split('.').next().unwrap_or(domain).to_string(),
+        Lang::En => domain.split('.').collect::<Vec<_>>().join(" dot "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registrable_domain() {
+        assert_eq!(registrable_domain("github.com"), Some("github.com"));
+        assert_eq!(registrable_domain("gist.github.com"), Some("github.com"));
+        assert_eq!(
+            registrable_domain("www.example.co.jp"),
+            Some("example.co.jp")
+        );
+        assert_eq!(registrable_domain("example.com."), Some("example.com"));
+
+        assert_eq!(registrable_domain("localhost"), None);
+        assert_eq!(registrable_domain("co.jp"), None);
+        assert_eq!(registrable_domain("192.168.0.1"), None);
+    }
+
+    #[test]
+    fn test_replace_url() {
+        let url = lang_t!("text.url", Lang::Ja);
+
+        assert_eq!(
+            replace_url("見て https://gist.github.com/abc です", Lang::Ja, true),
+            "見て github です"
+        );
+        assert_eq!(
+            replace_url("https://example.com:8443/path?q=1", Lang::Ja, true),
+            "example"
+        );
+        assert_eq!(
+            replace_url("<https://www.example.co.jp/page>", Lang::Ja, true),
+            "example"
+        );
+        assert_eq!(
+            replace_url("https://gist.github.com/abc", Lang::En, true),
+            "github dot com"
+        );
+
+        // ドメイン名を読めない場合や設定が OFF の場合は「URL」と読む
+        assert_eq!(replace_url("http://localhost:8080/", Lang::Ja, true), url);
+        assert_eq!(replace_url("http://192.168.0.1/", Lang::Ja, true), url);
+        assert_eq!(replace_url("https://github.com/", Lang::Ja, false), url);
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use sqlx::{sqlite::SqliteRow, Row, Sqlite, Transaction};
+
+/// 辞書の単語の一致方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MatchMode {
+    /// 文字列がそのまま含まれていれば置換する
+    #[default]
+    Literal,
+    /// 前後が英数字でない場合のみ置換する
+    Word,
+    /// 正規表現 (読み方に $1 などでキャプチャを使える)
+    Regex,
+}
+
+impl MatchMode {
+    pub const ALL: [MatchMode; 3] = [MatchMode::Literal, MatchMode::Word, MatchMode::Regex];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchMode::Literal => "literal",
+            MatchMode::Word => "word",
+            MatchMode::Regex => "regex",
+        }
+    }
+
+    pub fn from_name(s: &str) -> Option<MatchMode> {
+        Self::ALL.into_iter().find(|mode| mode.as_str() == s)
+    }
+}
+
+/// 辞書に登録された読み方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictEntry {
+    pub after_text: String,
+    pub mode: MatchMode,
+
+    /// 一致する範囲が重なった場合は大きいほうを優先する
+    pub priority: i64,
+}
+
+impl DictEntry {
+    pub fn new(after_text: impl Into<String>) -> DictEntry {
+        DictEntry {
+            after_text: after_text.into(),
+            mode: MatchMode::default(),
+            priority: 0,
+        }
+    }
+}
+
+// 0 は初期設定 (空の辞書) 用
+static DICT_VERSION: AtomicU64 = AtomicU64::new(1);
+
+/// 辞書を読み込んだり更新したりするたびに新しい値を返す
+pub(crate) fn next_dict_version() -> u64 {
+    DICT_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// 辞書テーブルから owner_column が owner_table_id の単語を取得する
+pub(crate) async fn fetch_dict(
+    tx: &mut Transaction<'_, Sqlite>,
+    table: &str,
+    owner_column: &str,
+    owner_table_id: u64,
+) -> Result<HashMap<String, DictEntry>, sqlx::Error> {
+    let dict = sqlx::query(&format!(
+        "
+        SELECT before_text, after_text, match_mode, priority FROM {table}
+        WHERE {owner_column} = ?1
+        "
+    ))
+    .bind(owner_table_id.to_string())
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(entry_from_row)
+    .collect();
+
+    Ok(dict)
+}
+
+/// before_text, after_text, match_mode, priority を取得した行から単語を作る
+pub(crate) fn entry_from_row(row: SqliteRow) -> (String, DictEntry) {
+    let mode: String = row.get("match_mode");
+    let entry = DictEntry {
+        after_text: row.get("after_text"),
+        mode: MatchMode::from_name(&mode).unwrap_or_default(),
+        priority: row.get("priority"),
+    };
+
+    (row.get("before_text"), entry)
+}
+
+/// 辞書テーブルの owner_column が owner_table_id の単語を dict で置き換える
+pub(crate) async fn replace_dict(
+    tx: &mut Transaction<'_, Sqlite>,
+    table: &str,
+    owner_column: &str,
+    owner_table_id: u64,
+    dict: HashMap<String, DictEntry>,
+) -> Result<(), sqlx::Error> {
+    let owner_table_id = owner_table_id.to_string();
+
+    sqlx::query(&format!("DELETE FROM {table} WHERE {owner_column} = ?1"))
+        .bind(&owner_table_id)
+        .execute(&mut **tx)
+        .await?;
+
+    for (before_text, entry) in dict {
+        sqlx::query(&format!(
+            "
+            INSERT INTO {table} ({owner_column}, before_text, after_text, match_mode, priority)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT ({owner_column}, before_text) DO NOTHING;
+            "
+        ))
+        .bind(&owner_table_id)
+        .bind(before_text)
+        .bind(entry.after_text)
+        .bind(entry.mode.as_str())
+        .bind(entry.priority)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
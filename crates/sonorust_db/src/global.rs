@@ -0,0 +1,160 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use tokio::sync::{RwLock as TokioRwLock, RwLockWriteGuard as TokioRwLockWriteGuard};
+
+use crate::{
+    dict::{entry_from_row, next_dict_version},
+    DictEntry, DB_POOL,
+};
+
+struct GlobalDictDatabase;
+impl GlobalDictDatabase {
+    async fn from() -> Result<GlobalDict, sqlx::Error> {
+        log::debug!("Access Global Dict Database Get");
+
+        let pool = DB_POOL.get().expect("Not initialaized DB_POOL");
+
+        let dict =
+            sqlx::query("SELECT before_text, after_text, match_mode, priority FROM global_dict")
+                .fetch_all(pool)
+                .await?
+                .into_iter()
+                .map(entry_from_row)
+                .collect();
+
+        Ok(GlobalDict {
+            dict,
+            dict_version: next_dict_version(),
+        })
+    }
+
+    async fn update(global_dict: GlobalDict) -> Result<(), sqlx::Error> {
+        log::debug!("Access Global Dict Database Update");
+
+        let pool = DB_POOL.get().expect("Not initialaized DB_POOL");
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM global_dict")
+            .execute(&mut *tx)
+            .await?;
+
+        for (before_text, entry) in global_dict.dict {
+            sqlx::query(
+                "
+                INSERT INTO global_dict (before_text, after_text, match_mode, priority)
+                VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT (before_text) DO NOTHING;
+                ",
+            )
+            .bind(before_text)
+            .bind(entry.after_text)
+            .bind(entry.mode.as_str())
+            .bind(entry.priority)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+static DB_CACHE: LazyLock<TokioRwLock<Option<GlobalDict>>> =
+    LazyLock::new(|| TokioRwLock::new(None));
+
+/// bot の所有者が管理する、全てのサーバーで使う辞書
+#[derive(Debug, Clone)]
+pub struct GlobalDict {
+    pub dict: HashMap<String, DictEntry>,
+
+    /// データベースから読み込んだり更新したりするたびに変わる値
+    /// (辞書から作ったデータをキャッシュするのに使う)
+    pub dict_version: u64,
+}
+
+impl GlobalDict {
+    pub async fn get() -> Result<GlobalDict, sqlx::Error> {
+        let cache_data = {
+            let db_cache = DB_CACHE.read().await;
+            db_cache.clone()
+        };
+
+        // cacheにあったなら取り出し、なければデータベースから取り出してキャッシュに入れる
+        let global_dict = match cache_data {
+            Some(data) => data,
+            None => {
+                let data = GlobalDictDatabase::from().await?;
+
+                let mut db_cache = DB_CACHE.write().await;
+                *db_cache = Some(data.clone());
+
+                data
+            }
+        };
+
+        Ok(global_dict)
+    }
+}
+
+#[derive(Debug)]
+pub struct GlobalDictMut<'a> {
+    pub dict: HashMap<String, DictEntry>,
+
+    cache_lock: TokioRwLockWriteGuard<'a, Option<GlobalDict>>,
+}
+
+impl GlobalDictMut<'_> {
+    pub async fn get<'a>() -> Result<GlobalDictMut<'a>, sqlx::Error> {
+        let global_dict = GlobalDict::get().await?;
+
+        Ok(GlobalDictMut {
+            dict: global_dict.dict,
+            cache_lock: DB_CACHE.write().await,
+        })
+    }
+
+    pub async fn update(self) -> Result<(), sqlx::Error> {
+        let mut db_cache = self.cache_lock;
+
+        let global_dict = GlobalDict {
+            dict: self.dict,
+            dict_version: next_dict_version(),
+        };
+
+        GlobalDictDatabase::update(global_dict.clone()).await?;
+        *db_cache = Some(global_dict);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_global_dict {
+    use tokio::fs::create_dir_all;
+
+    use crate::init_database;
+
+    use super::*;
+
+    #[ignore]
+    #[tokio::test]
+    async fn test_update() -> anyhow::Result<()> {
+        create_dir_all("appdata").await?;
+        init_database("appdata/database.db").await?;
+
+        for i in ["B1", "B2"] {
+            {
+                let mut global_dict_mut = GlobalDictMut::get().await?;
+                global_dict_mut
+                    .dict
+                    .insert("A1".to_string(), DictEntry::new(i));
+
+                global_dict_mut.update().await?;
+            }
+
+            dbg!(GlobalDict::get().await?);
+        }
+
+        Ok(())
+    }
+}
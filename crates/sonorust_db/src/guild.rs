@@ -1,11 +1,16 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 
-use serenity::all::{ChannelId, GuildId};
+use serenity::all::{ChannelId, GuildId, RoleId, UserId};
 use sqlx::Row;
 use tokio::sync::{RwLock as TokioRwLock, RwLockWriteGuard as TokioRwLockWriteGuard};
 
-use crate::DB_POOL;
+use crate::{
+    dict::{fetch_dict, next_dict_version, replace_dict},
+    history::record_dict_changes,
+    pipeline::{fetch_pipeline, replace_pipeline},
+    DictEntry, Permission, Pipeline, DB_POOL,
+};
 
 pub(crate) enum GuildOptionsStr {
     IsDicOnlyAdmin,
@@ -13,6 +18,13 @@ pub(crate) enum GuildOptionsStr {
     IsEntranceExitPlay,
     IsNoticeAttachment,
     IsIfLongFastRead,
+    IsReadVcChat,
+    IsRereadEdited,
+    IsReadName,
+    IsReadReply,
+    IsRomajiToKana,
+    IsSkipSpoiler,
+    IsReadUrlDomain,
 }
 
 impl GuildOptionsStr {
@@ -23,6 +35,13 @@ impl GuildOptionsStr {
             GuildOptionsStr::IsEntranceExitPlay => "is_entrance_exit_play",
             GuildOptionsStr::IsNoticeAttachment => "is_notice_attachment",
             GuildOptionsStr::IsIfLongFastRead => "is_if_long_fastread",
+            GuildOptionsStr::IsReadVcChat => "is_read_vc_chat",
+            GuildOptionsStr::IsRereadEdited => "is_reread_edited",
+            GuildOptionsStr::IsReadName => "is_read_name",
+            GuildOptionsStr::IsReadReply => "is_read_reply",
+            GuildOptionsStr::IsRomajiToKana => "is_romaji_to_kana",
+            GuildOptionsStr::IsSkipSpoiler => "is_skip_spoiler",
+            GuildOptionsStr::IsReadUrlDomain => "is_read_url_domain",
         }
     }
 }
@@ -53,14 +72,7 @@ impl GuildDatabase {
         let guild_table_id_string = guild_table_id.to_string();
 
         // サーバー辞書
-        let dict: HashMap<String, String> =
-            sqlx::query("SELECT before_text, after_text FROM guild_dict WHERE guild_table_id = ?1")
-                .bind(&guild_table_id_string)
-                .fetch_all(&mut *tx)
-                .await?
-                .into_iter()
-                .map(|row| (row.get("before_text"), row.get("after_text")))
-                .collect();
+        let dict = fetch_dict(&mut tx, "guild_dict", "guild_table_id", guild_table_id).await?;
 
         // サーバーオプション
         let mut options = GuildOptions::default();
@@ -85,6 +97,22 @@ impl GuildDatabase {
                 &mut options.is_notice_attachment,
                 GuildOptionsStr::IsNoticeAttachment,
             ),
+            (&mut options.is_read_vc_chat, GuildOptionsStr::IsReadVcChat),
+            (
+                &mut options.is_reread_edited,
+                GuildOptionsStr::IsRereadEdited,
+            ),
+            (&mut options.is_read_name, GuildOptionsStr::IsReadName),
+            (&mut options.is_read_reply, GuildOptionsStr::IsReadReply),
+            (
+                &mut options.is_romaji_to_kana,
+                GuildOptionsStr::IsRomajiToKana,
+            ),
+            (&mut options.is_skip_spoiler, GuildOptionsStr::IsSkipSpoiler),
+            (
+                &mut options.is_read_url_domain,
+                GuildOptionsStr::IsReadUrlDomain,
+            ),
         ];
 
         for (option_refm, option_name) in option_pairs {
@@ -137,17 +165,58 @@ impl GuildDatabase {
             // }
         }
 
+        // 読み上げないユーザー
+        let ignored_users: HashSet<UserId> =
+            sqlx::query("SELECT user_id FROM guild_ignored_user WHERE guild_table_id = ?1")
+                .bind(&guild_table_id_string)
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .map(|row| UserId::new(row.get("user_id")))
+                .collect();
+
+        // ロールごとの権限
+        let mut role_permissions: HashMap<Permission, HashSet<RoleId>> = HashMap::new();
+        let result = sqlx::query(
+            "SELECT role_id, permission FROM guild_role_permission WHERE guild_table_id = ?1",
+        )
+        .bind(&guild_table_id_string)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for row in result {
+            let role_id: u64 = row.get("role_id");
+            let permission: String = row.get("permission");
+
+            let Some(permission) = Permission::from_name(&permission) else {
+                continue;
+            };
+
+            role_permissions
+                .entry(permission)
+                .or_default()
+                .insert(RoleId::new(role_id));
+        }
+
+        // 読み上げの変換の順番
+        let pipeline = fetch_pipeline(&mut tx, guild_table_id).await?;
+
         tx.commit().await?;
 
         Ok(Some(GuildData {
             guild_id,
             dict,
             autojoin_channels,
+            ignored_users,
+            role_permissions,
             options,
+            pipeline,
+            dict_version: next_dict_version(),
         }))
     }
 
-    async fn update(guilddata: GuildData) -> Result<(), sqlx::Error> {
+    /// editor は辞書の変更履歴に残すユーザー
+    async fn update(guilddata: GuildData, editor: Option<UserId>) -> Result<(), sqlx::Error> {
         log::debug!("Access Guild Database Update - ID: {}", guilddata.guild_id);
 
         let pool = DB_POOL.get().expect("Not initialaized DB_POOL");
@@ -179,26 +248,18 @@ impl GuildDatabase {
         };
         let guild_table_id_string = guild_table_id.to_string();
 
-        // サーバー辞書更新
-        sqlx::query("DELETE FROM guild_dict WHERE guild_table_id = ?1")
-            .bind(&guild_table_id_string)
-            .execute(&mut *tx)
-            .await?;
+        // サーバー辞書の変更を履歴に追加してから更新
+        let old_dict = fetch_dict(&mut tx, "guild_dict", "guild_table_id", guild_table_id).await?;
+        record_dict_changes(&mut tx, guild_table_id, editor, &old_dict, &guilddata.dict).await?;
 
-        for (before_text, after_text) in guilddata.dict {
-            sqlx::query(
-                "
-                INSERT INTO guild_dict (guild_table_id, before_text, after_text)
-                VALUES (?1, ?2, ?3)
-                ON CONFLICT (guild_table_id, before_text) DO NOTHING;
-                ",
-            )
-            .bind(&guild_table_id_string)
-            .bind(before_text)
-            .bind(after_text)
-            .execute(&mut *tx)
-            .await?;
-        }
+        replace_dict(
+            &mut tx,
+            "guild_dict",
+            "guild_table_id",
+            guild_table_id,
+            guilddata.dict,
+        )
+        .await?;
 
         // ギルドオプション更新
         let insert_sql = "
@@ -232,6 +293,13 @@ impl GuildDatabase {
                 options.is_notice_attachment,
                 GuildOptionsStr::IsNoticeAttachment,
             ),
+            (options.is_read_vc_chat, GuildOptionsStr::IsReadVcChat),
+            (options.is_reread_edited, GuildOptionsStr::IsRereadEdited),
+            (options.is_read_name, GuildOptionsStr::IsReadName),
+            (options.is_read_reply, GuildOptionsStr::IsReadReply),
+            (options.is_romaji_to_kana, GuildOptionsStr::IsRomajiToKana),
+            (options.is_skip_spoiler, GuildOptionsStr::IsSkipSpoiler),
+            (options.is_read_url_domain, GuildOptionsStr::IsReadUrlDomain),
         ];
 
         for (option_bool, option_name) in option_pairs {
@@ -268,6 +336,53 @@ impl GuildDatabase {
                 .await?;
             }
         }
+
+        // 読み上げないユーザー更新
+        sqlx::query("DELETE FROM guild_ignored_user WHERE guild_table_id = ?1")
+            .bind(&guild_table_id_string)
+            .execute(&mut *tx)
+            .await?;
+
+        for user_id in guilddata.ignored_users {
+            sqlx::query(
+                "
+                INSERT INTO guild_ignored_user (guild_table_id, user_id)
+                VALUES (?1, ?2)
+                ON CONFLICT (guild_table_id, user_id) DO NOTHING;
+                ",
+            )
+            .bind(&guild_table_id_string)
+            .bind(user_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // ロールごとの権限更新
+        sqlx::query("DELETE FROM guild_role_permission WHERE guild_table_id = ?1")
+            .bind(&guild_table_id_string)
+            .execute(&mut *tx)
+            .await?;
+
+        for (permission, role_ids) in guilddata.role_permissions {
+            for role_id in role_ids {
+                sqlx::query(
+                    "
+                    INSERT INTO guild_role_permission (guild_table_id, role_id, permission)
+                    VALUES (?1, ?2, ?3)
+                    ON CONFLICT (guild_table_id, role_id, permission) DO NOTHING;
+                    ",
+                )
+                .bind(&guild_table_id_string)
+                .bind(role_id.to_string())
+                .bind(permission.as_str())
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        // 読み上げの変換の順番更新
+        replace_pipeline(&mut tx, guild_table_id, &guilddata.pipeline).await?;
+
         tx.commit().await?;
         Ok(())
     }
@@ -279,11 +394,24 @@ static DB_CACHE: LazyLock<TokioRwLock<HashMap<GuildId, Option<GuildData>>>> =
 #[derive(Debug, Clone)]
 pub struct GuildData {
     pub guild_id: GuildId,
-    pub dict: HashMap<String, String>,
+    pub dict: HashMap<String, DictEntry>,
 
     /// HashMap<VoiceChannelId, HashSet<読み上げるチャンネル>>
     pub autojoin_channels: HashMap<ChannelId, HashSet<ChannelId>>,
+
+    /// 読み上げないユーザー
+    pub ignored_users: HashSet<UserId>,
+
+    /// 権限ごとに許可されているロール
+    pub role_permissions: HashMap<Permission, HashSet<RoleId>>,
     pub options: GuildOptions,
+
+    /// 読み上げる文字を変換する順番
+    pub pipeline: Pipeline,
+
+    /// データベースから読み込んだり更新したりするたびに変わる値
+    /// (辞書から作ったデータをキャッシュするのに使う)
+    pub dict_version: u64,
 }
 
 impl GuildData {
@@ -329,6 +457,28 @@ impl GuildData {
             dict: HashMap::new(),
             options: GuildOptions::default(),
             autojoin_channels: HashMap::new(),
+            ignored_users: HashSet::new(),
+            role_permissions: HashMap::new(),
+            pipeline: Pipeline::default(),
+            dict_version: 0,
+        }
+    }
+
+    /// ロールによって権限が与えられているか
+    /// (ロールが設定されていない場合は既定の動作に従う、管理者などの確認は呼び出し側で行う)
+    pub fn is_permitted(&self, permission: Permission, member_roles: &[RoleId]) -> bool {
+        let roles = self
+            .role_permissions
+            .get(&permission)
+            .filter(|roles| !roles.is_empty());
+
+        match roles {
+            Some(roles) => member_roles.iter().any(|role_id| roles.contains(role_id)),
+            None => match permission {
+                Permission::ManageDict => !self.options.is_dic_onlyadmin,
+                Permission::ManageServer => false,
+                Permission::Clear | Permission::BeRead => true,
+            },
         }
     }
 }
@@ -336,12 +486,21 @@ impl GuildData {
 #[derive(Debug)]
 pub struct GuildDataMut<'a> {
     pub guild_id: GuildId,
-    pub dict: HashMap<String, String>,
+    pub dict: HashMap<String, DictEntry>,
 
     /// HashMap<VoiceChannelId, HashSet<読み上げるチャンネル>>
     pub autojoin_channels: HashMap<ChannelId, HashSet<ChannelId>>,
+
+    /// 読み上げないユーザー
+    pub ignored_users: HashSet<UserId>,
+
+    /// 権限ごとに許可されているロール
+    pub role_permissions: HashMap<Permission, HashSet<RoleId>>,
     pub options: GuildOptions,
 
+    /// 読み上げる文字を変換する順番
+    pub pipeline: Pipeline,
+
     cache_lock: TokioRwLockWriteGuard<'a, HashMap<GuildId, Option<GuildData>>>,
 }
 
@@ -353,12 +512,24 @@ impl GuildDataMut<'_> {
             guild_id,
             dict: guilddata.dict,
             autojoin_channels: guilddata.autojoin_channels,
+            ignored_users: guilddata.ignored_users,
+            role_permissions: guilddata.role_permissions,
             options: guilddata.options,
+            pipeline: guilddata.pipeline,
             cache_lock: DB_CACHE.write().await,
         })
     }
 
     pub async fn update(self) -> Result<(), sqlx::Error> {
+        self.update_with_editor(None).await
+    }
+
+    /// 辞書の変更を user_id による変更として履歴に残して更新する
+    pub async fn update_by(self, user_id: UserId) -> Result<(), sqlx::Error> {
+        self.update_with_editor(Some(user_id)).await
+    }
+
+    async fn update_with_editor(self, editor: Option<UserId>) -> Result<(), sqlx::Error> {
         let mut db_cache = self.cache_lock;
 
         let guild_data = GuildData {
@@ -366,9 +537,13 @@ impl GuildDataMut<'_> {
             dict: self.dict,
             options: self.options,
             autojoin_channels: self.autojoin_channels,
+            ignored_users: self.ignored_users,
+            role_permissions: self.role_permissions,
+            pipeline: self.pipeline,
+            dict_version: next_dict_version(),
         };
 
-        GuildDatabase::update(guild_data.clone()).await?;
+        GuildDatabase::update(guild_data.clone(), editor).await?;
         db_cache.insert(self.guild_id, Some(guild_data));
 
         Ok(())
@@ -382,6 +557,13 @@ pub struct GuildOptions {
     pub is_entrance_exit_play: bool,
     pub is_notice_attachment: bool,
     pub is_if_long_fastread: bool,
+    pub is_read_vc_chat: bool,
+    pub is_reread_edited: bool,
+    pub is_read_name: bool,
+    pub is_read_reply: bool,
+    pub is_romaji_to_kana: bool,
+    pub is_skip_spoiler: bool,
+    pub is_read_url_domain: bool,
 }
 
 impl Default for GuildOptions {
@@ -392,6 +574,13 @@ impl Default for GuildOptions {
             is_entrance_exit_play: false,
             is_notice_attachment: false,
             is_if_long_fastread: false,
+            is_read_vc_chat: false,
+            is_reread_edited: false,
+            is_read_name: false,
+            is_read_reply: false,
+            is_romaji_to_kana: false,
+            is_skip_spoiler: false,
+            is_read_url_domain: false,
         }
     }
 }
@@ -400,7 +589,7 @@ impl Default for GuildOptions {
 mod tests_guild_data_base {
     use tokio::fs::create_dir_all;
 
-    use crate::init_database;
+    use crate::{init_database, MatchMode};
 
     use super::*;
 
@@ -410,11 +599,20 @@ mod tests_guild_data_base {
         create_dir_all("appdata").await?;
         init_database("appdata/database.db").await?;
 
-        let dict: HashMap<_, _> = [("A1", "B1"), ("A2", "B2"), ("A3", "B3")]
+        let mut dict: HashMap<_, _> = [("A1", "B1"), ("A2", "B2"), ("A3", "B3")]
             .iter()
-            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .map(|(k, v)| (k.to_string(), DictEntry::new(*v)))
             .collect();
 
+        dict.insert(
+            "w+$".to_string(),
+            DictEntry {
+                after_text: "わら".to_string(),
+                mode: MatchMode::Regex,
+                priority: 1,
+            },
+        );
+
         let autojoin_channels: HashMap<ChannelId, HashSet<ChannelId>> = HashMap::from([
             (
                 12345.into(),
@@ -426,18 +624,35 @@ mod tests_guild_data_base {
             ),
         ]);
 
-        GuildDatabase::update(GuildData {
-            guild_id: GuildId::new(123),
-            dict,
-            options: GuildOptions {
-                is_dic_onlyadmin: true,
-                is_entrance_exit_log: true,
-                is_entrance_exit_play: false,
-                is_notice_attachment: true,
-                is_if_long_fastread: false,
+        GuildDatabase::update(
+            GuildData {
+                guild_id: GuildId::new(123),
+                dict,
+                options: GuildOptions {
+                    is_dic_onlyadmin: true,
+                    is_entrance_exit_log: true,
+                    is_entrance_exit_play: false,
+                    is_notice_attachment: true,
+                    is_if_long_fastread: false,
+                    is_read_vc_chat: false,
+                    is_reread_edited: false,
+                    is_read_name: false,
+                    is_read_reply: false,
+                    is_romaji_to_kana: false,
+                    is_skip_spoiler: false,
+                    is_read_url_domain: false,
+                },
+                autojoin_channels,
+                ignored_users: HashSet::from([1.into(), 2.into()]),
+                role_permissions: HashMap::from([(
+                    Permission::ManageDict,
+                    HashSet::from([3.into()]),
+                )]),
+                pipeline: Pipeline::default(),
+                dict_version: 0,
             },
-            autojoin_channels,
-        })
+            None,
+        )
         .await?;
 
         Ok(())
@@ -550,3 +765,31 @@ mod tests_guilddata_mut {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests_permission {
+    use super::*;
+
+    #[test]
+    fn test_is_permitted() {
+        let mut guilddata = GuildData::default_settings(GuildId::new(1));
+
+        // ロールが設定されていなければ既定の動作
+        assert!(!guilddata.is_permitted(Permission::ManageDict, &[]));
+        assert!(!guilddata.is_permitted(Permission::ManageServer, &[]));
+        assert!(guilddata.is_permitted(Permission::Clear, &[]));
+        assert!(guilddata.is_permitted(Permission::BeRead, &[]));
+
+        guilddata.options.is_dic_onlyadmin = false;
+        assert!(guilddata.is_permitted(Permission::ManageDict, &[]));
+
+        // ロールが設定されていればそのロールを持つ人のみ
+        guilddata
+            .role_permissions
+            .insert(Permission::BeRead, HashSet::from([RoleId::new(10)]));
+
+        assert!(!guilddata.is_permitted(Permission::BeRead, &[]));
+        assert!(!guilddata.is_permitted(Permission::BeRead, &[RoleId::new(11)]));
+        assert!(guilddata.is_permitted(Permission::BeRead, &[RoleId::new(11), RoleId::new(10)]));
+    }
+}
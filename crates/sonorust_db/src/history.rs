@@ -0,0 +1,207 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serenity::all::{GuildId, UserId};
+use sqlx::{sqlite::SqliteRow, Row, Sqlite, Transaction};
+
+use crate::{DictEntry, MatchMode, DB_POOL};
+
+/// サーバー辞書の変更の記録
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictChange {
+    pub id: i64,
+
+    /// 変更したユーザー (分からない場合は None)
+    pub user_id: Option<UserId>,
+
+    /// 変更した時刻 (UNIX 時間の秒)
+    pub changed_at: i64,
+    pub word: String,
+
+    /// 変更前の読み方 (None なら新しく追加された)
+    pub old: Option<DictEntry>,
+
+    /// 変更後の読み方 (None なら削除された)
+    pub new: Option<DictEntry>,
+}
+
+impl DictChange {
+    /// 新しい順に最大 limit 件の変更を取得する
+    pub async fn list(guild_id: GuildId, limit: u32) -> Result<Vec<DictChange>, sqlx::Error> {
+        log::debug!("Access Dict History Database Get - ID: {guild_id}");
+
+        let pool = DB_POOL.get().expect("Not initialaized DB_POOL");
+
+        let changes = sqlx::query(
+            "
+            SELECT guild_dict_history.* FROM guild_dict_history
+            INNER JOIN guild ON guild.id = guild_dict_history.guild_table_id
+            WHERE guild.discord_id = ?1
+            ORDER BY guild_dict_history.id DESC
+            LIMIT ?2
+            ",
+        )
+        .bind(guild_id.to_string())
+        .bind(limit)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(change_from_row)
+        .collect();
+
+        Ok(changes)
+    }
+
+    /// id の変更を取得する (他のサーバーの変更は取得しない)
+    pub async fn get(guild_id: GuildId, id: i64) -> Result<Option<DictChange>, sqlx::Error> {
+        log::debug!("Access Dict History Database Get - ID: {guild_id}, Change: {id}");
+
+        let pool = DB_POOL.get().expect("Not initialaized DB_POOL");
+
+        let change = sqlx::query(
+            "
+            SELECT guild_dict_history.* FROM guild_dict_history
+            INNER JOIN guild ON guild.id = guild_dict_history.guild_table_id
+            WHERE guild.discord_id = ?1 AND guild_dict_history.id = ?2
+            ",
+        )
+        .bind(guild_id.to_string())
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .map(change_from_row);
+
+        Ok(change)
+    }
+}
+
+fn change_from_row(row: SqliteRow) -> DictChange {
+    let entry = |prefix: &str| {
+        let after_text: Option<String> = row.get(format!("{prefix}_after_text").as_str());
+        let mode: Option<String> = row.get(format!("{prefix}_match_mode").as_str());
+        let priority: Option<i64> = row.get(format!("{prefix}_priority").as_str());
+
+        after_text.map(|after_text| DictEntry {
+            after_text,
+            mode: mode
+                .and_then(|mode| MatchMode::from_name(&mode))
+                .unwrap_or_default(),
+            priority: priority.unwrap_or(0),
+        })
+    };
+
+    let user_id: Option<u64> = row.get("user_id");
+
+    DictChange {
+        id: row.get("id"),
+        user_id: user_id.map(UserId::new),
+        changed_at: row.get("changed_at"),
+        word: row.get("before_text"),
+        old: entry("old"),
+        new: entry("new"),
+    }
+}
+
+/// old から new への変更を履歴に追加する (変更のない単語は追加しない)
+pub(crate) async fn record_dict_changes(
+    tx: &mut Transaction<'_, Sqlite>,
+    guild_table_id: u64,
+    user_id: Option<UserId>,
+    old: &HashMap<String, DictEntry>,
+    new: &HashMap<String, DictEntry>,
+) -> Result<(), sqlx::Error> {
+    let changed_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0);
+
+    // 履歴の順番が毎回同じになるように単語順に追加する
+    let words: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    for word in words {
+        let (old_entry, new_entry) = (old.get(word), new.get(word));
+
+        if old_entry == new_entry {
+            continue;
+        }
+
+        sqlx::query(
+            "
+            INSERT INTO guild_dict_history (
+                guild_table_id, user_id, changed_at, before_text,
+                old_after_text, old_match_mode, old_priority,
+                new_after_text, new_match_mode, new_priority
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10);
+            ",
+        )
+        .bind(guild_table_id.to_string())
+        .bind(user_id.map(|user_id| user_id.to_string()))
+        .bind(changed_at)
+        .bind(word)
+        .bind(old_entry.map(|entry| entry.after_text.as_str()))
+        .bind(old_entry.map(|entry| entry.mode.as_str()))
+        .bind(old_entry.map(|entry| entry.priority))
+        .bind(new_entry.map(|entry| entry.after_text.as_str()))
+        .bind(new_entry.map(|entry| entry.mode.as_str()))
+        .bind(new_entry.map(|entry| entry.priority))
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_dict_history {
+    use tokio::fs::create_dir_all;
+
+    use crate::{init_database, GuildDataMut};
+
+    use super::*;
+
+    #[ignore]
+    #[tokio::test]
+    async fn test_record() -> anyhow::Result<()> {
+        create_dir_all("appdata").await?;
+        init_database("appdata/database.db").await?;
+
+        let guild_id = GuildId::new(1);
+        let user_id = UserId::new(2);
+
+        for after_text in [Some("B1"), Some("B2"), None] {
+            let mut guilddata_mut = GuildDataMut::from(guild_id).await?;
+
+            match after_text {
+                Some(after_text) => {
+                    guilddata_mut
+                        .dict
+                        .insert("A1".to_string(), DictEntry::new(after_text));
+                }
+                None => {
+                    guilddata_mut.dict.remove("A1");
+                }
+            }
+
+            guilddata_mut.update_by(user_id).await?;
+        }
+
+        let changes = DictChange::list(guild_id, 3).await?;
+        dbg!(&changes);
+
+        let latest = DictChange::get(guild_id, changes[0].id).await?;
+        assert_eq!(latest.as_ref(), changes.first());
+
+        let latest = latest.unwrap();
+        assert_eq!(latest.user_id, Some(user_id));
+        assert_eq!(latest.old, Some(DictEntry::new("B2")));
+        assert_eq!(latest.new, None);
+
+        // 他のサーバーの変更は取得できない
+        assert_eq!(DictChange::get(GuildId::new(3), latest.id).await?, None);
+
+        Ok(())
+    }
+}
@@ -1,9 +1,23 @@
+mod dict;
+mod global;
 mod guild;
+mod history;
+mod permission;
+mod pipeline;
 mod user;
 
+pub use dict::DictEntry;
+pub use dict::MatchMode;
+pub use global::GlobalDict;
+pub use global::GlobalDictMut;
 pub use guild::GuildData;
 pub use guild::GuildDataMut;
 pub use guild::GuildOptions;
+pub use history::DictChange;
+pub use permission::Permission;
+pub use pipeline::Pipeline;
+pub use pipeline::PipelineStage;
+pub use pipeline::StageSetting;
 pub use user::UserData;
 pub use user::UserDataMut;
 
@@ -11,7 +25,7 @@ use guild::GuildOptionsStr;
 
 use std::{fs::File, path::PathBuf, str::FromStr, sync::OnceLock};
 
-use sqlx::{sqlite::SqliteConnectOptions, SqlitePool};
+use sqlx::{sqlite::SqliteConnectOptions, Row, Sqlite, SqlitePool, Transaction};
 
 static DB_POOL: OnceLock<SqlitePool> = OnceLock::new();
 
@@ -36,7 +50,9 @@ pub async fn init_database(database_path: &str) -> Result<(), sqlx::Error> {
             model_name TEXT NOT NULL,
             speaker_name TEXT NOT NULL,
             style_name TEXT NOT NULL,
-            length REAL NOT NULL
+            length REAL NOT NULL,
+            read_name TEXT,
+            is_read_disabled INTEGER NOT NULL DEFAULT 0
         );
         ",
         // guild table
@@ -72,11 +88,69 @@ pub async fn init_database(database_path: &str) -> Result<(), sqlx::Error> {
             guild_table_id INTEGER NOT NULL,
             before_text TEXT NOT NULL,
             after_text TEXT NOT NULL,
+            match_mode TEXT NOT NULL DEFAULT 'literal',
+            priority INTEGER NOT NULL DEFAULT 0,
 
             FOREIGN KEY (guild_table_id) REFERENCES guild(id),
             UNIQUE (guild_table_id, before_text)
         );
         ",
+        // user_dict table
+        "
+        CREATE TABLE IF NOT EXISTS user_dict (
+            id INTEGER PRIMARY KEY,
+            user_table_id INTEGER NOT NULL,
+            before_text TEXT NOT NULL,
+            after_text TEXT NOT NULL,
+            match_mode TEXT NOT NULL DEFAULT 'literal',
+            priority INTEGER NOT NULL DEFAULT 0,
+
+            FOREIGN KEY (user_table_id) REFERENCES user(id),
+            UNIQUE (user_table_id, before_text)
+        );
+        ",
+        // global_dict table (全てのサーバーで使う辞書)
+        "
+        CREATE TABLE IF NOT EXISTS global_dict (
+            id INTEGER PRIMARY KEY,
+            before_text TEXT NOT NULL UNIQUE,
+            after_text TEXT NOT NULL,
+            match_mode TEXT NOT NULL DEFAULT 'literal',
+            priority INTEGER NOT NULL DEFAULT 0
+        );
+        ",
+        // guild_dict_history table (サーバー辞書の変更履歴、追加のみ)
+        "
+        CREATE TABLE IF NOT EXISTS guild_dict_history (
+            id INTEGER PRIMARY KEY,
+            guild_table_id INTEGER NOT NULL,
+            user_id INTEGER,
+            changed_at INTEGER NOT NULL,
+            before_text TEXT NOT NULL,
+            old_after_text TEXT,
+            old_match_mode TEXT,
+            old_priority INTEGER,
+            new_after_text TEXT,
+            new_match_mode TEXT,
+            new_priority INTEGER,
+
+            FOREIGN KEY (guild_table_id) REFERENCES guild(id)
+        );
+        ",
+        "
+        CREATE TRIGGER IF NOT EXISTS guild_dict_history_no_update
+        BEFORE UPDATE ON guild_dict_history
+        BEGIN
+            SELECT RAISE(ABORT, 'guild_dict_history is append-only');
+        END;
+        ",
+        "
+        CREATE TRIGGER IF NOT EXISTS guild_dict_history_no_delete
+        BEFORE DELETE ON guild_dict_history
+        BEGIN
+            SELECT RAISE(ABORT, 'guild_dict_history is append-only');
+        END;
+        ",
         // guild_auto_join
         "
         CREATE TABLE IF NOT EXISTS guild_auto_join (
@@ -89,12 +163,64 @@ pub async fn init_database(database_path: &str) -> Result<(), sqlx::Error> {
             UNIQUE (voice_channel_id, text_channel_id)
         );
         ",
+        // guild_ignored_user
+        "
+        CREATE TABLE IF NOT EXISTS guild_ignored_user (
+            id INTEGER PRIMARY KEY,
+            guild_table_id INTEGER NOT NULL,
+            user_id INTEGER NOT NULL,
+
+            FOREIGN KEY (guild_table_id) REFERENCES guild(id),
+            UNIQUE (guild_table_id, user_id)
+        );
+        ",
+        // guild_role_permission
+        "
+        CREATE TABLE IF NOT EXISTS guild_role_permission (
+            id INTEGER PRIMARY KEY,
+            guild_table_id INTEGER NOT NULL,
+            role_id INTEGER NOT NULL,
+            permission TEXT NOT NULL,
+
+            FOREIGN KEY (guild_table_id) REFERENCES guild(id),
+            UNIQUE (guild_table_id, role_id, permission)
+        );
+        ",
+        // guild_pipeline_stage
+        "
+        CREATE TABLE IF NOT EXISTS guild_pipeline_stage (
+            id INTEGER PRIMARY KEY,
+            guild_table_id INTEGER NOT NULL,
+            stage_name TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            is_enabled INTEGER NOT NULL DEFAULT 1,
+
+            FOREIGN KEY (guild_table_id) REFERENCES guild(id),
+            UNIQUE (guild_table_id, stage_name)
+        );
+        ",
     ];
 
     for i in sqls {
         sqlx::query(i).execute(&mut *tx).await?;
     }
 
+    // 後から追加したカラム (既存のデータベース用)
+    let add_columns = [
+        ("user", "read_name", "TEXT"),
+        ("user", "is_read_disabled", "INTEGER NOT NULL DEFAULT 0"),
+        (
+            "guild_dict",
+            "match_mode",
+            "TEXT NOT NULL DEFAULT 'literal'",
+        ),
+        ("guild_dict", "priority", "INTEGER NOT NULL DEFAULT 0"),
+    ];
+
+    for (table, column, column_type) in add_columns {
+        add_column_if_not_exists(&mut tx, table, column, column_type).await?;
+    }
+
     // ギルドオプションの追加
     let guild_options = [
         GuildOptionsStr::IsDicOnlyAdmin,
@@ -102,6 +228,13 @@ pub async fn init_database(database_path: &str) -> Result<(), sqlx::Error> {
         GuildOptionsStr::IsEntranceExitPlay,
         GuildOptionsStr::IsIfLongFastRead,
         GuildOptionsStr::IsNoticeAttachment,
+        GuildOptionsStr::IsReadVcChat,
+        GuildOptionsStr::IsRereadEdited,
+        GuildOptionsStr::IsReadName,
+        GuildOptionsStr::IsReadReply,
+        GuildOptionsStr::IsRomajiToKana,
+        GuildOptionsStr::IsSkipSpoiler,
+        GuildOptionsStr::IsReadUrlDomain,
     ];
 
     for i in guild_options {
@@ -111,12 +244,41 @@ pub async fn init_database(database_path: &str) -> Result<(), sqlx::Error> {
             .await?;
     }
 
+    // 以前はギルドオプションだった段階の ON/OFF を移す
+    pipeline::migrate_option_stages(&mut tx).await?;
+
     tx.commit().await?;
 
     DB_POOL.set(pool).expect("Failed to set DB_POOL");
     Ok(())
 }
 
+/// テーブルにカラムがなければ追加する
+async fn add_column_if_not_exists(
+    tx: &mut Transaction<'_, Sqlite>,
+    table: &str,
+    column: &str,
+    column_type: &str,
+) -> Result<(), sqlx::Error> {
+    let rows = sqlx::query(&format!("PRAGMA table_info({table})"))
+        .fetch_all(&mut **tx)
+        .await?;
+
+    let exists = rows
+        .iter()
+        .any(|row| row.get::<String, _>("name") == column);
+
+    if !exists {
+        sqlx::query(&format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {column_type}"
+        ))
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
 mod tests {
     #[ignore]
     #[tokio::test]
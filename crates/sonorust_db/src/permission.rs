@@ -0,0 +1,36 @@
+/// ロールに割り当てられる権限
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// サーバー辞書の編集
+    ManageDict,
+    /// サーバー設定の変更
+    ManageServer,
+    /// /clear の使用
+    Clear,
+    /// メッセージを読み上げられる
+    BeRead,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 4] = [
+        Permission::ManageDict,
+        Permission::ManageServer,
+        Permission::Clear,
+        Permission::BeRead,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ManageDict => "manage_dict",
+            Permission::ManageServer => "manage_server",
+            Permission::Clear => "clear",
+            Permission::BeRead => "be_read",
+        }
+    }
+
+    pub fn from_name(s: &str) -> Option<Permission> {
+        Self::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == s)
+    }
+}
@@ -0,0 +1,450 @@
+use sqlx::{Row, Sqlite, Transaction};
+
+/// 読み上げる文字を変換する段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PipelineStage {
+    /// コードブロックとインラインコード
+    Codeblock,
+    /// 見出しや太字などのマークダウン
+    Markdown,
+    /// 音声の生成でエラーになる文字
+    FixError,
+    /// URL
+    Url,
+    /// メンション、チャンネル、カスタム絵文字など
+    DiscordObject,
+    GlobalDict,
+    GuildDict,
+    UserDict,
+    /// 顔文字 (初期設定では無効)
+    Kaomoji,
+    /// www や 草 (初期設定では無効)
+    Laugh,
+    /// 同じ文字の繰り返し (初期設定では無効)
+    CollapseRepeat,
+    /// 続いた記号 (初期設定では無効)
+    LimitPunctuation,
+    /// 絵文字の読み (初期設定では無効)
+    Emoji,
+    /// 数、日付、単位
+    Number,
+    /// 英語のカタカナ読み (日本語のときのみ)
+    EngToKana,
+    /// 読めない記号の削除
+    RemoveSymbol,
+}
+
+impl PipelineStage {
+    /// 初期設定の順番
+    pub const ALL: [PipelineStage; 16] = [
+        PipelineStage::Codeblock,
+        PipelineStage::Markdown,
+        PipelineStage::FixError,
+        PipelineStage::Url,
+        PipelineStage::DiscordObject,
+        PipelineStage::GlobalDict,
+        PipelineStage::GuildDict,
+        PipelineStage::UserDict,
+        PipelineStage::Kaomoji,
+        PipelineStage::Laugh,
+        PipelineStage::CollapseRepeat,
+        PipelineStage::LimitPunctuation,
+        PipelineStage::Emoji,
+        PipelineStage::Number,
+        PipelineStage::EngToKana,
+        PipelineStage::RemoveSymbol,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PipelineStage::Codeblock => "codeblock",
+            PipelineStage::Markdown => "markdown",
+            PipelineStage::FixError => "fix_error",
+            PipelineStage::Url => "url",
+            PipelineStage::DiscordObject => "discord_object",
+            PipelineStage::GlobalDict => "global_dict",
+            PipelineStage::GuildDict => "guild_dict",
+            PipelineStage::UserDict => "user_dict",
+            PipelineStage::Kaomoji => "kaomoji",
+            PipelineStage::Laugh => "laugh",
+            PipelineStage::CollapseRepeat => "collapse_repeat",
+            PipelineStage::LimitPunctuation => "limit_punctuation",
+            PipelineStage::Emoji => "emoji",
+            PipelineStage::Number => "number",
+            PipelineStage::EngToKana => "eng_to_kana",
+            PipelineStage::RemoveSymbol => "remove_symbol",
+        }
+    }
+
+    pub fn from_name(s: &str) -> Option<PipelineStage> {
+        Self::ALL.into_iter().find(|stage| stage.as_str() == s)
+    }
+
+    /// 初期設定で有効かどうか
+    /// (読み方が変わるものは OFF)
+    pub fn is_enabled_by_default(&self) -> bool {
+        !matches!(
+            self,
+            PipelineStage::Kaomoji
+                | PipelineStage::Laugh
+                | PipelineStage::CollapseRepeat
+                | PipelineStage::LimitPunctuation
+                | PipelineStage::Emoji
+        )
+    }
+}
+
+/// 以前はギルドオプションで ON/OFF していた段階
+const FORMER_OPTION_STAGES: [(&str, PipelineStage); 5] = [
+    ("is_remove_kaomoji", PipelineStage::Kaomoji),
+    ("is_read_laugh", PipelineStage::Laugh),
+    ("is_collapse_repeat", PipelineStage::CollapseRepeat),
+    ("is_limit_punctuation", PipelineStage::LimitPunctuation),
+    ("is_read_emoji", PipelineStage::Emoji),
+];
+
+/// 段階と有効かどうか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageSetting {
+    pub stage: PipelineStage,
+    pub is_enabled: bool,
+}
+
+/// サーバーごとの変換の順番
+/// (全ての段階が1回ずつ含まれる)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pipeline {
+    stages: Vec<StageSetting>,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        let stages = PipelineStage::ALL
+            .into_iter()
+            .map(|stage| StageSetting {
+                stage,
+                is_enabled: stage.is_enabled_by_default(),
+            })
+            .collect();
+
+        Self { stages }
+    }
+}
+
+impl Pipeline {
+    /// 保存されていた順番から作る
+    /// 重複は最初のものを使い、含まれていない段階は初期設定で直前にある段階の後ろに初期設定の ON/OFF で追加する
+    pub fn from_settings(settings: impl IntoIterator<Item = StageSetting>) -> Pipeline {
+        let mut stages: Vec<StageSetting> = vec![];
+
+        for setting in settings {
+            if !stages.iter().any(|s| s.stage == setting.stage) {
+                stages.push(setting);
+            }
+        }
+
+        for (i, stage) in PipelineStage::ALL.into_iter().enumerate() {
+            if stages.iter().any(|s| s.stage == stage) {
+                continue;
+            }
+
+            let insert_at = PipelineStage::ALL[..i]
+                .iter()
+                .rev()
+                .find_map(|prev| stages.iter().position(|s| s.stage == *prev))
+                .map(|position| position + 1)
+                .unwrap_or(0);
+
+            stages.insert(
+                insert_at,
+                StageSetting {
+                    stage,
+                    is_enabled: stage.is_enabled_by_default(),
+                },
+            );
+        }
+
+        Pipeline { stages }
+    }
+
+    /// 順番通りの全ての段階
+    pub fn stages(&self) -> &[StageSetting] {
+        &self.stages
+    }
+
+    /// 順番通りの有効な段階
+    pub fn enabled_stages(&self) -> impl Iterator<Item = PipelineStage> + '_ {
+        self.stages.iter().filter(|s| s.is_enabled).map(|s| s.stage)
+    }
+
+    pub fn is_enabled(&self, stage: PipelineStage) -> bool {
+        self.stages.iter().any(|s| s.stage == stage && s.is_enabled)
+    }
+
+    pub fn set_enabled(&mut self, stage: PipelineStage, is_enabled: bool) {
+        if let Some(setting) = self.stages.iter_mut().find(|s| s.stage == stage) {
+            setting.is_enabled = is_enabled;
+        }
+    }
+
+    /// stage を offset だけ前 (負) か後ろ (正) に移動する
+    /// (端を超える場合は端で止める)
+    pub fn move_stage(&mut self, stage: PipelineStage, offset: isize) {
+        let Some(from) = self.stages.iter().position(|s| s.stage == stage) else {
+            return;
+        };
+
+        let to = from
+            .saturating_add_signed(offset)
+            .min(self.stages.len() - 1);
+
+        let setting = self.stages.remove(from);
+        self.stages.insert(to, setting);
+    }
+}
+
+pub(crate) async fn fetch_pipeline(
+    tx: &mut Transaction<'_, Sqlite>,
+    guild_table_id: u64,
+) -> Result<Pipeline, sqlx::Error> {
+    let rows = sqlx::query(
+        "
+        SELECT stage_name, is_enabled FROM guild_pipeline_stage
+        WHERE guild_table_id = ?1
+        ORDER BY position
+        ",
+    )
+    .bind(guild_table_id.to_string())
+    .fetch_all(&mut **tx)
+    .await?;
+
+    // 知らない段階 (古いバージョンで削除されたものなど) は無視する
+    let settings = rows.into_iter().filter_map(|row| {
+        let stage_name: String = row.get("stage_name");
+        let is_enabled: bool = row.get("is_enabled");
+
+        PipelineStage::from_name(&stage_name).map(|stage| StageSetting { stage, is_enabled })
+    });
+
+    Ok(Pipeline::from_settings(settings))
+}
+
+pub(crate) async fn replace_pipeline(
+    tx: &mut Transaction<'_, Sqlite>,
+    guild_table_id: u64,
+    pipeline: &Pipeline,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM guild_pipeline_stage WHERE guild_table_id = ?1")
+        .bind(guild_table_id.to_string())
+        .execute(&mut **tx)
+        .await?;
+
+    // 初期設定と同じなら保存しない (後から段階が増えても初期設定の順番になるように)
+    if *pipeline == Pipeline::default() {
+        return Ok(());
+    }
+
+    for (position, setting) in pipeline.stages().iter().enumerate() {
+        sqlx::query(
+            "
+            INSERT INTO guild_pipeline_stage (guild_table_id, stage_name, position, is_enabled)
+            VALUES (?1, ?2, ?3, ?4);
+            ",
+        )
+        .bind(guild_table_id.to_string())
+        .bind(setting.stage.as_str())
+        .bind(position as i64)
+        .bind(setting.is_enabled)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// ギルドオプションの ON/OFF を変換の順番の設定に移す
+/// (移したオプションは削除するので、一度だけ実行される)
+pub(crate) async fn migrate_option_stages(
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<(), sqlx::Error> {
+    for (option_name, stage) in FORMER_OPTION_STAGES {
+        let option_table_id: Option<u64> =
+            sqlx::query("SELECT id FROM guild_option WHERE option_name = ?1")
+                .bind(option_name)
+                .fetch_optional(&mut **tx)
+                .await?
+                .map(|row| row.get("id"));
+
+        let Some(option_table_id) = option_table_id else {
+            continue;
+        };
+
+        // オプションが ON のサーバーと、段階を保存しているサーバー
+        let guild_table_ids: Vec<u64> = sqlx::query(
+            "
+            SELECT guild_table_id FROM guild_guild_options WHERE guild_option_table_id = ?1
+            UNION
+            SELECT guild_table_id FROM guild_pipeline_stage WHERE stage_name = ?2
+            ",
+        )
+        .bind(option_table_id.to_string())
+        .bind(stage.as_str())
+        .fetch_all(&mut **tx)
+        .await?
+        .into_iter()
+        .map(|row| row.get("guild_table_id"))
+        .collect();
+
+        for guild_table_id in guild_table_ids {
+            let is_option_enabled = sqlx::query(
+                "
+                SELECT 1 FROM guild_guild_options
+                WHERE guild_table_id = ?1 AND guild_option_table_id = ?2
+                ",
+            )
+            .bind(guild_table_id.to_string())
+            .bind(option_table_id.to_string())
+            .fetch_optional(&mut **tx)
+            .await?
+            .is_some();
+
+            // 保存されていなければ以前の初期設定 (有効) だった
+            let is_stage_enabled: bool = sqlx::query(
+                "
+                SELECT is_enabled FROM guild_pipeline_stage
+                WHERE guild_table_id = ?1 AND stage_name = ?2
+                ",
+            )
+            .bind(guild_table_id.to_string())
+            .bind(stage.as_str())
+            .fetch_optional(&mut **tx)
+            .await?
+            .is_none_or(|row| row.get("is_enabled"));
+
+            let mut pipeline = fetch_pipeline(tx, guild_table_id).await?;
+            pipeline.set_enabled(stage, is_option_enabled && is_stage_enabled);
+            replace_pipeline(tx, guild_table_id, &pipeline).await?;
+        }
+
+        sqlx::query("DELETE FROM guild_guild_options WHERE guild_option_table_id = ?1")
+            .bind(option_table_id.to_string())
+            .execute(&mut **tx)
+            .await?;
+
+        sqlx::query("DELETE FROM guild_option WHERE id = ?1")
+            .bind(option_table_id.to_string())
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_pipeline {
+    use super::*;
+
+    #[test]
+    fn test_from_settings() {
+        let settings = [
+            StageSetting {
+                stage: PipelineStage::Url,
+                is_enabled: false,
+            },
+            StageSetting {
+                stage: PipelineStage::Codeblock,
+                is_enabled: true,
+            },
+            StageSetting {
+                stage: PipelineStage::Url,
+                is_enabled: true,
+            },
+        ];
+
+        let pipeline = Pipeline::from_settings(settings);
+        let stages: Vec<_> = pipeline.stages().iter().map(|s| s.stage).collect();
+
+        // 全ての段階が1回ずつ含まれる
+        assert_eq!(stages.len(), PipelineStage::ALL.len());
+
+        // 含まれていない段階は初期設定で直前にある段階の後ろに入る
+        // (DiscordObject 以降は Url の後ろ、Markdown と FixError は Codeblock の後ろ)
+        assert_eq!(
+            &stages[..2],
+            &[PipelineStage::Url, PipelineStage::DiscordObject]
+        );
+        assert_eq!(
+            &stages[stages.len() - 3..],
+            &[
+                PipelineStage::Codeblock,
+                PipelineStage::Markdown,
+                PipelineStage::FixError
+            ]
+        );
+        assert!(!pipeline.is_enabled(PipelineStage::Url));
+        assert!(pipeline.is_enabled(PipelineStage::Markdown));
+
+        // 読み方が変わる段階は追加されても無効
+        assert!(!pipeline.is_enabled(PipelineStage::Kaomoji));
+        assert!(!pipeline.is_enabled(PipelineStage::Emoji));
+    }
+
+    #[test]
+    fn test_move_stage() {
+        let mut pipeline = Pipeline::default();
+
+        pipeline.move_stage(PipelineStage::Url, -10);
+        assert_eq!(pipeline.stages()[0].stage, PipelineStage::Url);
+
+        pipeline.move_stage(PipelineStage::Url, 1);
+        assert_eq!(pipeline.stages()[1].stage, PipelineStage::Url);
+
+        pipeline.move_stage(PipelineStage::Codeblock, 100);
+        assert_eq!(
+            pipeline.stages().last().map(|s| s.stage),
+            Some(PipelineStage::Codeblock)
+        );
+
+        pipeline.set_enabled(PipelineStage::Number, false);
+        assert!(!pipeline
+            .enabled_stages()
+            .any(|s| s == PipelineStage::Number));
+    }
+}
+
+#[cfg(test)]
+mod tests_guild_pipeline {
+    use serenity::all::GuildId;
+    use tokio::fs::create_dir_all;
+
+    use crate::{init_database, GuildData, GuildDataMut};
+
+    use super::*;
+
+    #[ignore]
+    #[tokio::test]
+    async fn test_update() -> anyhow::Result<()> {
+        create_dir_all("appdata").await?;
+        init_database("appdata/database.db").await?;
+
+        let guild_id = GuildId::new(1);
+
+        {
+            let mut guilddata_mut = GuildDataMut::from(guild_id).await?;
+            guilddata_mut.pipeline = Pipeline::default();
+            guilddata_mut
+                .pipeline
+                .set_enabled(PipelineStage::Number, false);
+            guilddata_mut.pipeline.move_stage(PipelineStage::Url, -3);
+            guilddata_mut.update().await?;
+        }
+
+        let guilddata = GuildData::from(guild_id).await?;
+        dbg!(&guilddata.pipeline);
+
+        assert_eq!(guilddata.pipeline.stages()[0].stage, PipelineStage::Url);
+        assert!(!guilddata.pipeline.is_enabled(PipelineStage::Number));
+
+        Ok(())
+    }
+}
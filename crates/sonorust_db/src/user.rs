@@ -4,7 +4,10 @@ use serenity::all::UserId;
 use sqlx::Row;
 use tokio::sync::{RwLock as TokioRwLock, RwLockWriteGuard as TokioRwLockWriteGuard};
 
-use crate::DB_POOL;
+use crate::{
+    dict::{fetch_dict, next_dict_version, replace_dict},
+    DictEntry, DB_POOL,
+};
 
 struct UserDatabase;
 impl UserDatabase {
@@ -19,20 +22,33 @@ impl UserDatabase {
         let mut tx = pool.begin().await?;
 
         let result = sqlx::query(
-            "SELECT model_name, speaker_name, style_name, length 
+            "SELECT id, model_name, speaker_name, style_name, length, read_name, is_read_disabled
                      FROM user WHERE discord_id = ?1;",
         )
         .bind(user_id.to_string())
         .fetch_optional(&mut *tx)
         .await?;
 
-        let userdata = result.map(|row| UserData {
-            user_id,
-            model_name: row.get("model_name"),
-            speaker_name: row.get("speaker_name"),
-            style_name: row.get("style_name"),
-            length: row.get("length"),
-        });
+        let userdata = match result {
+            Some(row) => {
+                // 個人辞書
+                let user_table_id: u64 = row.get("id");
+                let dict = fetch_dict(&mut tx, "user_dict", "user_table_id", user_table_id).await?;
+
+                Some(UserData {
+                    user_id,
+                    model_name: row.get("model_name"),
+                    speaker_name: row.get("speaker_name"),
+                    style_name: row.get("style_name"),
+                    length: row.get("length"),
+                    read_name: row.get("read_name"),
+                    is_read_disabled: row.get("is_read_disabled"),
+                    dict,
+                    dict_version: next_dict_version(),
+                })
+            }
+            None => None,
+        };
 
         tx.commit().await?;
         Ok(userdata)
@@ -44,17 +60,40 @@ impl UserDatabase {
         let pool = DB_POOL.get().expect("Not initialaized DB_POOL");
         let mut tx = pool.begin().await?;
 
-        sqlx::query(
-            "INSERT OR REPLACE INTO
-                 user (discord_id, model_name, speaker_name, style_name, length)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
+        // 個人辞書が参照しているので id が変わらないように更新する
+        let user_table_id: u64 = sqlx::query(
+            "INSERT INTO
+                 user (discord_id, model_name, speaker_name, style_name, length,
+                       read_name, is_read_disabled)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT (discord_id) DO UPDATE SET
+                     model_name = excluded.model_name,
+                     speaker_name = excluded.speaker_name,
+                     style_name = excluded.style_name,
+                     length = excluded.length,
+                     read_name = excluded.read_name,
+                     is_read_disabled = excluded.is_read_disabled
+                 RETURNING id",
         )
         .bind(userdata.user_id.to_string())
         .bind(userdata.model_name)
         .bind(userdata.speaker_name)
         .bind(userdata.style_name)
         .bind(userdata.length)
-        .execute(&mut *tx)
+        .bind(userdata.read_name)
+        .bind(userdata.is_read_disabled)
+        .fetch_one(&mut *tx)
+        .await?
+        .get("id");
+
+        // 個人辞書更新
+        replace_dict(
+            &mut tx,
+            "user_dict",
+            "user_table_id",
+            user_table_id,
+            userdata.dict,
+        )
         .await?;
 
         tx.commit().await?;
@@ -72,6 +111,19 @@ pub struct UserData {
     pub speaker_name: String,
     pub style_name: String,
     pub length: f64,
+
+    /// 名前を読み上げるときに使う読み方
+    pub read_name: Option<String>,
+
+    /// true の場合このユーザーのメッセージは読み上げない
+    pub is_read_disabled: bool,
+
+    /// このユーザーのメッセージにだけ使う辞書
+    pub dict: HashMap<String, DictEntry>,
+
+    /// データベースから読み込んだり更新したりするたびに変わる値
+    /// (辞書から作ったデータをキャッシュするのに使う)
+    pub dict_version: u64,
 }
 
 impl UserData {
@@ -122,6 +174,10 @@ impl UserData {
             speaker_name: "None".to_string(),
             style_name: "None".to_string(),
             length: 1.0,
+            read_name: None,
+            is_read_disabled: false,
+            dict: HashMap::new(),
+            dict_version: 0,
         }
     }
 }
@@ -133,6 +189,9 @@ pub struct UserDataMut<'a> {
     pub speaker_name: String,
     pub style_name: String,
     pub length: f64,
+    pub read_name: Option<String>,
+    pub is_read_disabled: bool,
+    pub dict: HashMap<String, DictEntry>,
 
     cache_lock: TokioRwLockWriteGuard<'a, HashMap<UserId, Option<UserData>>>,
 }
@@ -147,6 +206,9 @@ impl UserDataMut<'_> {
             speaker_name: user_data.speaker_name,
             style_name: user_data.style_name,
             length: user_data.length,
+            read_name: user_data.read_name,
+            is_read_disabled: user_data.is_read_disabled,
+            dict: user_data.dict,
             cache_lock: DB_CACHE.write().await,
         })
     }
@@ -160,6 +222,10 @@ impl UserDataMut<'_> {
             speaker_name: self.speaker_name,
             style_name: self.style_name,
             length: self.length,
+            read_name: self.read_name,
+            is_read_disabled: self.is_read_disabled,
+            dict: self.dict,
+            dict_version: next_dict_version(),
         };
 
         UserDatabase::update(user_data.clone()).await?;
@@ -190,6 +256,10 @@ mod tests_user_data_base {
             speaker_name: "speaker_name2".to_string(),
             style_name: "style_name3".to_string(),
             length: 1.5,
+            read_name: None,
+            is_read_disabled: false,
+            dict: HashMap::from([("A1".to_string(), DictEntry::new("B1"))]),
+            dict_version: 0,
         })
         .await?;
 
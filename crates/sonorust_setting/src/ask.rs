@@ -10,7 +10,11 @@ use crossterm::{
 };
 use dialoguer::{Confirm, Input, Select};
 
-use crate::setting_json::{BotLang, InferLang, InferUse, SettingJson};
+use crate::setting_json::{
+    default_emoji_repeat_limit, default_engtokana_acronym_max_len, default_engtokana_spell_max_len,
+    default_read_name_interval, default_reply_excerpt_limit, BotLang, InferLang, InferUse,
+    SettingJson,
+};
 
 pub fn ask_to_create_setting_json() -> anyhow::Result<SettingJson> {
     print!("Your Bot Token: ");
@@ -128,6 +132,12 @@ pub fn ask_to_create_setting_json() -> anyhow::Result<SettingJson> {
                 read_limit,
                 fastread_limit,
                 wav_read_limit,
+                emoji_repeat_limit: default_emoji_repeat_limit(),
+                read_name_interval: default_read_name_interval(),
+                reply_excerpt_limit: default_reply_excerpt_limit(),
+                is_download_engtokana_dic: false,
+                engtokana_spell_max_len: default_engtokana_spell_max_len(),
+                engtokana_acronym_max_len: default_engtokana_acronym_max_len(),
                 default_model,
                 prefix,
                 bot_lang,
@@ -193,6 +203,12 @@ pub fn ask_to_create_setting_json() -> anyhow::Result<SettingJson> {
                 read_limit,
                 fastread_limit,
                 wav_read_limit,
+                emoji_repeat_limit: default_emoji_repeat_limit(),
+                read_name_interval: default_read_name_interval(),
+                reply_excerpt_limit: default_reply_excerpt_limit(),
+                is_download_engtokana_dic: false,
+                engtokana_spell_max_len: default_engtokana_spell_max_len(),
+                engtokana_acronym_max_len: default_engtokana_acronym_max_len(),
                 default_model,
                 prefix,
                 bot_lang,
@@ -60,6 +60,19 @@ pub struct SettingJson {
     pub read_limit: u32,
     pub wav_read_limit: u32,
     pub fastread_limit: u32,
+    #[serde(default = "default_emoji_repeat_limit")]
+    pub emoji_repeat_limit: u32,
+    #[serde(default = "default_read_name_interval")]
+    pub read_name_interval: u64,
+    #[serde(default = "default_reply_excerpt_limit")]
+    pub reply_excerpt_limit: u32,
+    // bep-eng.dic をダウンロードしてカタカナ読み辞書に追加する (初期設定は同梱の辞書のみ)
+    #[serde(default)]
+    pub is_download_engtokana_dic: bool,
+    #[serde(default = "default_engtokana_spell_max_len")]
+    pub engtokana_spell_max_len: u32,
+    #[serde(default = "default_engtokana_acronym_max_len")]
+    pub engtokana_acronym_max_len: u32,
     pub default_model: String,
     pub prefix: String,
     pub bot_lang: BotLang,
@@ -77,6 +90,31 @@ pub struct SettingJson {
     pub is_gpu_version_runtime: bool,
 }
 
+// 同じ絵文字が連続したときに読み上げる最大数
+pub(crate) fn default_emoji_repeat_limit() -> u32 {
+    3
+}
+
+// 同じ人の発言でも、この秒数以上間が空いたら名前を読み上げる
+pub(crate) fn default_read_name_interval() -> u64 {
+    60
+}
+
+// 返信先のメッセージを読み上げる最大文字数 (0 の場合は読まない)
+pub(crate) fn default_reply_excerpt_limit() -> u32 {
+    20
+}
+
+// 辞書にない英単語や略語を1文字ずつ読む最大文字数 (0 の場合は1文字ずつ読まない)
+pub(crate) fn default_engtokana_spell_max_len() -> u32 {
+    6
+}
+
+// 辞書にあっても1文字ずつ読む大文字だけの略語の最大文字数 (0 の場合は辞書を優先する)
+pub(crate) fn default_engtokana_acronym_max_len() -> u32 {
+    3
+}
+
 impl SettingJson {
     pub async fn init<P>(json_path: P) -> anyhow::Result<SettingJson>
     where